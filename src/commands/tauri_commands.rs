@@ -1,6 +1,12 @@
-use crate::plugins::plugin_obs::{ObsPlugin, ObsConnectionConfig, ObsWebSocketVersion};
+use crate::plugins::plugin_obs::{
+    default_quality_profiles_path, default_recording_directory, mask_stream_key, BulkOperationResult,
+    ObsConfigSnapshot, ObsConnectionConfig, ObsConnectionRole, ObsConnectionStatus, ObsHeartbeatConfig, ObsPlugin,
+    ObsWebSocketVersion, QualityProfile, SceneItemTransform, VideoSettings, DEFAULT_STREAM_DROP_THRESHOLD,
+};
+use crate::i18n;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::mpsc;
 // use tauri::State; // Commented out since we removed tauri dependency
 
@@ -16,13 +22,27 @@ pub struct AddConnectionRequest {
     pub password: Option<String>,
     pub protocol_version: String,
     pub enabled: bool,
+    #[serde(default)]
+    pub recording_indicator_source: Option<String>,
+    #[serde(default)]
+    pub scene_collection: Option<String>,
+    #[serde(default)]
+    pub auto_start_replay_buffer: bool,
+    #[serde(default)]
+    pub pause_recording_on_break: bool,
+    #[serde(default)]
+    pub role: Option<ObsConnectionRole>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConnectionStatus {
     pub connection_name: String,
     pub status: String,
+    pub status_label: String,
     pub error: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub replay_buffer_active: Option<bool>,
+    pub role: Option<ObsConnectionRole>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,10 +52,22 @@ pub struct ObsResponse {
     pub error: Option<String>,
 }
 
-// Initialize OBS plugin
+// Initialize OBS plugin; starts the heartbeat when called inside a Tokio runtime
 pub fn init_obs_plugin() -> ObsPluginState {
     let (event_tx, _event_rx) = mpsc::unbounded_channel();
     let plugin = ObsPlugin::new(event_tx);
+
+    if let Some(path) = default_quality_profiles_path() {
+        if let Err(e) = plugin.load_quality_profiles(&path) {
+            eprintln!("Failed to load OBS quality profiles: {}", e);
+        }
+    }
+
+    if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+        let heartbeat_plugin = plugin.clone();
+        runtime.spawn(async move { heartbeat_plugin.run_heartbeat().await });
+    }
+
     Arc::new(Mutex::new(Some(plugin)))
 }
 
@@ -220,6 +252,11 @@ pub async fn add_obs_connection(
         password: request.password,
         protocol_version,
         enabled: request.enabled,
+        recording_indicator_source: request.recording_indicator_source,
+        scene_collection: request.scene_collection,
+        auto_start_replay_buffer: request.auto_start_replay_buffer,
+        pause_recording_on_break: request.pause_recording_on_break,
+        role: request.role,
     };
 
     // Add connection
@@ -235,4 +272,464 @@ pub async fn add_obs_connection(
             error: Some(e),
         }),
     }
-} 
\ No newline at end of file
+}
+
+pub fn get_obs_connections(plugin: &ObsPlugin) -> Vec<ConnectionStatus> {
+    plugin.get_connection_names()
+        .into_iter()
+        .filter_map(|name| {
+            let status = plugin.get_connection_status(&name)?;
+            let status_label = i18n::t(match &status {
+                ObsConnectionStatus::Disconnected => "status.disconnected",
+                ObsConnectionStatus::Connecting => "status.connecting",
+                ObsConnectionStatus::Connected => "status.connected",
+                ObsConnectionStatus::Authenticating => "status.authenticating",
+                ObsConnectionStatus::Authenticated => "status.authenticated",
+                ObsConnectionStatus::Error(_) => "status.error",
+            });
+            let (status, error) = match status {
+                ObsConnectionStatus::Error(e) => ("Error".to_string(), Some(e)),
+                other => (format!("{:?}", other), None),
+            };
+
+            Some(ConnectionStatus {
+                latency_ms: plugin.get_last_ping(&name),
+                replay_buffer_active: plugin.get_replay_buffer_state(&name),
+                role: plugin.get_connection_role(&name),
+                connection_name: name,
+                status,
+                status_label,
+                error,
+            })
+        })
+        .collect()
+}
+
+pub async fn ping_obs_connection(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.ping(&connection_name).await
+        .map(|latency_ms| serde_json::json!({ "latency_ms": latency_ms }));
+
+    Ok(to_obs_response(result))
+}
+
+pub fn get_obs_heartbeat_config(plugin: &ObsPlugin) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(Ok(plugin.get_heartbeat_config())))
+}
+
+pub fn set_obs_heartbeat_config(
+    plugin: &ObsPlugin,
+    config: ObsHeartbeatConfig,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.set_heartbeat_config(config).map(|_| config)))
+}
+
+pub async fn start_all_obs_recording(plugin: &ObsPlugin) -> Result<ObsResponse, String> {
+    Ok(bulk_operation_response(plugin.start_all_recording().await))
+}
+
+pub async fn stop_all_obs_recording(plugin: &ObsPlugin) -> Result<ObsResponse, String> {
+    Ok(bulk_operation_response(plugin.stop_all_recording().await))
+}
+
+pub async fn change_all_obs_scenes(
+    plugin: &ObsPlugin,
+    scene_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(bulk_operation_response(plugin.change_all_scenes(&scene_name).await))
+}
+
+pub async fn get_obs_scene_item_transform(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    scene_name: String,
+    item_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_scene_item_transform(&connection_name, &scene_name, &item_name).await))
+}
+
+pub async fn set_obs_scene_item_transform(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    scene_name: String,
+    item_name: String,
+    transform: SceneItemTransform,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.set_scene_item_transform(&connection_name, &scene_name, &item_name, transform).await))
+}
+
+pub async fn start_obs_virtual_camera(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.start_virtual_camera(&connection_name).await
+        .map(|is_active| serde_json::json!({ "is_active": is_active }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn stop_obs_virtual_camera(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.stop_virtual_camera(&connection_name).await
+        .map(|is_active| serde_json::json!({ "is_active": is_active }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn get_obs_virtual_camera_status(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.get_virtual_camera_status(&connection_name).await
+        .map(|is_active| serde_json::json!({ "is_active": is_active }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn stop_obs_recording(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.stop_recording(&connection_name).await
+        .map(|output_path| serde_json::json!({ "output_path": output_path }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn test_obs_connection_config(
+    plugin: &ObsPlugin,
+    host: String,
+    port: u16,
+    password: Option<String>,
+    protocol_version: String,
+) -> Result<ObsResponse, String> {
+    let protocol_version = match protocol_version.as_str() {
+        "v4" => ObsWebSocketVersion::V4,
+        "v5" => ObsWebSocketVersion::V5,
+        _ => return Err("Invalid protocol version. Must be 'v4' or 'v5'".to_string()),
+    };
+
+    Ok(to_obs_response(plugin.test_connection_config(&host, port, password.as_deref(), protocol_version).await))
+}
+
+pub async fn get_obs_stream_health(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_stream_health(&connection_name).await))
+}
+
+// Watch a stream in the background, emitting StreamHealthWarning events.
+// Replaces any monitor already running on the connection.
+pub fn start_obs_stream_health_monitor(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    interval_ms: u64,
+    threshold: Option<f64>,
+) -> Result<ObsResponse, String> {
+    let threshold = threshold.unwrap_or(DEFAULT_STREAM_DROP_THRESHOLD);
+    let result = plugin.start_stream_health_monitor(&connection_name, Duration::from_millis(interval_ms), threshold)
+        .map(|_| serde_json::json!({ "threshold": threshold }));
+
+    Ok(to_obs_response(result))
+}
+
+pub fn stop_obs_stream_health_monitor(plugin: &ObsPlugin, connection_name: String) -> Result<ObsResponse, String> {
+    let result = plugin.stop_stream_health_monitor(&connection_name)
+        .map(|stopped| serde_json::json!({ "stopped": stopped }));
+
+    Ok(to_obs_response(result))
+}
+
+pub fn set_locale(locale: String) -> Result<ObsResponse, String> {
+    let result = i18n::set_locale(&locale)
+        .map(|_| serde_json::json!({ "locale": i18n::get_locale() }));
+
+    Ok(to_obs_response(result))
+}
+
+pub fn get_locale() -> Result<ObsResponse, String> {
+    Ok(to_obs_response(Ok(serde_json::json!({
+        "locale": i18n::get_locale(),
+        "supported": i18n::SUPPORTED_LOCALES,
+    }))))
+}
+
+pub async fn set_obs_text_source(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    source_name: String,
+    text: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.set_text_source(&connection_name, &source_name, &text).await
+        .map(|text| serde_json::json!({ "text": text }));
+
+    Ok(to_obs_response(result))
+}
+
+pub fn save_obs_quality_profile(
+    plugin: &ObsPlugin,
+    profile: QualityProfile,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.save_quality_profile(profile)))
+}
+
+pub fn get_obs_quality_profiles(plugin: &ObsPlugin) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(Ok(plugin.get_quality_profiles())))
+}
+
+pub async fn apply_obs_quality_profile(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    profile_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.apply_quality_profile(&connection_name, &profile_name).await))
+}
+
+pub async fn set_obs_recording_directory(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    directory: Option<String>,
+) -> Result<ObsResponse, String> {
+    let directory = match directory.filter(|d| !d.trim().is_empty()) {
+        Some(directory) => directory,
+        // This machine's Videos folder means nothing to a remote OBS
+        None if !plugin.is_local_connection(&connection_name)? => {
+            return Err("No recording path given; a default is only available when OBS runs on this machine".to_string());
+        }
+        None => match default_recording_directory() {
+            Some(directory) => directory.to_string_lossy().to_string(),
+            None => return Err("No recording path given and no default Videos folder found".to_string()),
+        },
+    };
+
+    let result = plugin.set_recording_directory(&connection_name, &directory).await
+        .map(|directory| serde_json::json!({ "directory": directory }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn set_obs_studio_mode(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    enabled: bool,
+) -> Result<ObsResponse, String> {
+    let result = plugin.set_studio_mode(&connection_name, enabled).await
+        .map(|enabled| serde_json::json!({ "enabled": enabled }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn set_obs_preview_scene(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    scene_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.set_preview_scene(&connection_name, &scene_name).await))
+}
+
+pub async fn trigger_obs_transition(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.trigger_transition(&connection_name).await))
+}
+
+pub async fn get_obs_profiles(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_profiles(&connection_name).await))
+}
+
+pub async fn get_obs_current_profile(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.get_current_profile(&connection_name).await
+        .map(|profile_name| serde_json::json!({ "profile_name": profile_name }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn set_obs_current_profile(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    profile_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.set_current_profile(&connection_name, &profile_name).await
+        .map(|profile_name| serde_json::json!({ "profile_name": profile_name }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn get_obs_scene_collections(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_scene_collections(&connection_name).await))
+}
+
+pub async fn set_obs_scene_collection(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    collection_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.set_scene_collection(&connection_name, &collection_name).await
+        .map(|scenes| serde_json::json!({ "collection_name": collection_name, "scenes": scenes }));
+
+    Ok(to_obs_response(result))
+}
+
+pub fn get_obs_capabilities(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.get_capabilities(&connection_name)
+        .ok_or_else(|| format!("Capabilities for '{}' have not been detected", connection_name));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn export_obs_config(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.export_config(&connection_name).await))
+}
+
+pub async fn import_obs_config(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    snapshot: ObsConfigSnapshot,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.import_config(&connection_name, &snapshot).await))
+}
+
+pub async fn get_obs_recording_progress(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_recording_progress(&connection_name).await))
+}
+
+// Pause/resume the recording connection's recording for a match break
+pub async fn obs_match_break(plugin: &ObsPlugin, in_break: bool) -> Result<ObsResponse, String> {
+    let result = plugin.handle_match_break_for_recording(in_break).await
+        .map(|changed| serde_json::json!({ "changed": changed }));
+
+    Ok(to_obs_response(result))
+}
+
+// Emit RecordingProgress events in the background until recording stops.
+// Replaces any listener already running on the connection.
+pub fn setup_obs_recording_progress_listener(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    interval_ms: u64,
+) -> Result<ObsResponse, String> {
+    let result = plugin.start_recording_progress_listener(&connection_name, Duration::from_millis(interval_ms))
+        .map(|_| serde_json::json!({ "interval_ms": interval_ms }));
+
+    Ok(to_obs_response(result))
+}
+
+pub fn stop_obs_recording_progress_listener(plugin: &ObsPlugin, connection_name: String) -> Result<ObsResponse, String> {
+    let result = plugin.stop_recording_progress_listener(&connection_name)
+        .map(|stopped| serde_json::json!({ "stopped": stopped }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn get_obs_input_kinds(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_input_kinds(&connection_name).await))
+}
+
+pub async fn get_obs_input_properties(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    input_name: String,
+    property_name: Option<String>,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_input_properties(&connection_name, &input_name, property_name.as_deref()).await))
+}
+
+pub async fn set_obs_stream_service_settings(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    service: String,
+    server: String,
+    key: String,
+) -> Result<ObsResponse, String> {
+    let result = plugin.set_stream_service_settings(&connection_name, &service, &server, &key).await
+        .map(|_| serde_json::json!({ "key": mask_stream_key(&key) }));
+
+    Ok(to_obs_response(result))
+}
+
+pub async fn get_obs_video_settings(
+    plugin: &ObsPlugin,
+    connection_name: String,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_video_settings(&connection_name).await))
+}
+
+pub async fn set_obs_video_settings(
+    plugin: &ObsPlugin,
+    connection_name: String,
+    settings: VideoSettings,
+) -> Result<ObsResponse, String> {
+    let result = plugin.set_video_settings(&connection_name, settings).await
+        .map(|(settings, warnings)| serde_json::json!({ "settings": settings, "warnings": warnings }));
+
+    Ok(to_obs_response(result))
+}
+
+pub fn get_obs_connection_for_role(
+    plugin: &ObsPlugin,
+    role: ObsConnectionRole,
+) -> Result<ObsResponse, String> {
+    Ok(to_obs_response(plugin.get_connection_for_role(role)))
+}
+
+// Aggregate per-connection results into a single response
+fn bulk_operation_response(results: Vec<BulkOperationResult>) -> ObsResponse {
+    let failed = results.iter().filter(|r| !r.success).count();
+
+    ObsResponse {
+        success: failed == 0,
+        data: Some(serde_json::json!({
+            "succeeded": results.len() - failed,
+            "failed": failed,
+            "results": results,
+        })),
+        error: if failed > 0 {
+            Some(i18n::t_args("bulk.connections_failed", &[&failed.to_string(), &results.len().to_string()]))
+        } else {
+            None
+        },
+    }
+}
+
+// Wrap a plugin result in the standard response shape
+fn to_obs_response<T: Serialize>(result: Result<T, String>) -> ObsResponse {
+    match result {
+        Ok(value) => ObsResponse {
+            success: true,
+            data: serde_json::to_value(value).ok(),
+            error: None,
+        },
+        Err(e) => ObsResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        },
+    }
+}
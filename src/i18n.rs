@@ -0,0 +1,123 @@
+// Message catalog for user-facing backend strings
+use std::sync::RwLock;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+static CURRENT_LOCALE: RwLock<String> = RwLock::new(String::new());
+
+// (key, English, Slovenian); an empty translation falls back to English
+const CATALOG: &[(&str, &str, &str)] = &[
+    ("status.disconnected", "Disconnected", "Ni povezave"),
+    ("status.connecting", "Connecting", "Povezovanje"),
+    ("status.connected", "Connected", "Povezano"),
+    ("status.authenticating", "Authenticating", "Preverjanje pristnosti"),
+    ("status.authenticated", "Authenticated", "Overjeno"),
+    ("status.error", "Error", "Napaka"),
+    ("bulk.connections_failed", "{0} of {1} connections failed", "Neuspešne povezave: {0} od {1}"),
+];
+
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "sl"];
+
+// Set the active locale
+pub fn set_locale(locale: &str) -> Result<(), String> {
+    if !SUPPORTED_LOCALES.contains(&locale) {
+        return Err(format!(
+            "Unsupported locale '{}'. Supported: {}",
+            locale,
+            SUPPORTED_LOCALES.join(", ")
+        ));
+    }
+
+    *CURRENT_LOCALE.write().unwrap() = locale.to_string();
+    Ok(())
+}
+
+// Get the active locale
+pub fn get_locale() -> String {
+    let locale = CURRENT_LOCALE.read().unwrap();
+    if locale.is_empty() {
+        DEFAULT_LOCALE.to_string()
+    } else {
+        locale.clone()
+    }
+}
+
+// Look up a message in the active locale, falling back to English and then the key itself
+pub fn t(key: &str) -> String {
+    translate(&get_locale(), key)
+}
+
+// Look up a message and substitute positional {0}, {1}, ... arguments
+pub fn t_args(key: &str, args: &[&str]) -> String {
+    substitute(t(key), args)
+}
+
+fn substitute(message: String, args: &[&str]) -> String {
+    args.iter()
+        .enumerate()
+        .fold(message, |message, (i, arg)| message.replace(&format!("{{{}}}", i), arg))
+}
+
+fn translate(locale: &str, key: &str) -> String {
+    translate_from(CATALOG, locale, key)
+}
+
+fn translate_from(catalog: &[(&str, &str, &str)], locale: &str, key: &str) -> String {
+    let entry = match catalog.iter().find(|(k, _, _)| *k == key) {
+        Some(entry) => entry,
+        None => return key.to_string(),
+    };
+
+    let translated = match locale {
+        "sl" => entry.2,
+        _ => entry.1,
+    };
+
+    if translated.is_empty() {
+        entry.1.to_string()
+    } else {
+        translated.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_resolve_in_each_locale() {
+        assert_eq!(translate("en", "status.connected"), "Connected");
+        assert_eq!(translate("sl", "status.connected"), "Povezano");
+    }
+
+    #[test]
+    fn missing_translations_fall_back_per_key() {
+        let catalog = [
+            ("greeting", "Hello", "Živjo"),
+            ("farewell", "Goodbye", ""),
+        ];
+
+        assert_eq!(translate_from(&catalog, "sl", "greeting"), "Živjo");
+        assert_eq!(translate_from(&catalog, "sl", "farewell"), "Goodbye");
+        assert_eq!(translate_from(&catalog, "de", "greeting"), "Hello");
+        assert_eq!(translate_from(&catalog, "sl", "unknown"), "unknown");
+    }
+
+    #[test]
+    fn unsupported_locales_are_rejected() {
+        assert!(set_locale("de").is_err());
+        assert!(SUPPORTED_LOCALES.contains(&get_locale().as_str()));
+    }
+
+    #[test]
+    fn arguments_are_substituted() {
+        assert_eq!(
+            substitute(translate("en", "bulk.connections_failed"), &["1", "3"]),
+            "1 of 3 connections failed"
+        );
+        assert_eq!(
+            substitute(translate("sl", "bulk.connections_failed"), &["1", "3"]),
+            "Neuspešne povezave: 1 od 3"
+        );
+    }
+}
@@ -14,6 +14,9 @@ use plugins::plugin_udp;
 mod commands;
 use commands::tauri_commands;
 
+// Localized backend strings
+mod i18n;
+
 fn handle_client(mut stream: TcpStream) {
     let mut buffer = [0; 1024];
     match stream.read(&mut buffer) {
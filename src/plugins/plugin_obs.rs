@@ -1,6 +1,15 @@
 use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use base64::Engine;
+use futures_util::stream::SplitStream;
+use futures_util::{SinkExt, StreamExt};
+use sha2::{Digest, Sha256};
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::{self, Message};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -20,6 +29,71 @@ pub struct ObsConnectionConfig {
     pub password: Option<String>,
     pub protocol_version: ObsWebSocketVersion,
     pub enabled: bool,
+    // Text source updated with a "REC" indicator when recording starts/stops
+    #[serde(default)]
+    pub recording_indicator_source: Option<String>,
+    // Scene collection to switch to once connected
+    #[serde(default)]
+    pub scene_collection: Option<String>,
+    // Start the replay buffer on connect and restart it if it stops unexpectedly
+    // (recording and replay connections only)
+    #[serde(default)]
+    pub auto_start_replay_buffer: bool,
+    // Pause (not stop) recording during match breaks so the output stays one file
+    #[serde(default)]
+    pub pause_recording_on_break: bool,
+    // What this connection is used for; inferred from the name when unset
+    #[serde(default)]
+    pub role: Option<ObsConnectionRole>,
+}
+
+impl ObsConnectionConfig {
+    // Explicit role, or one inferred from the name for configs saved before roles existed
+    pub fn effective_role(&self) -> ObsConnectionRole {
+        self.role.unwrap_or_else(|| ObsConnectionRole::infer_from_name(&self.name))
+    }
+
+    // Whether this connection keeps a replay buffer running on its own
+    pub fn keeps_replay_buffer_running(&self) -> bool {
+        self.auto_start_replay_buffer
+            && matches!(self.effective_role(), ObsConnectionRole::Recording | ObsConnectionRole::Replay)
+    }
+}
+
+// What a connection is used for by the automatic flows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ObsConnectionRole {
+    Recording,
+    Streaming,
+    Replay,
+    General,
+}
+
+impl ObsConnectionRole {
+    // Roles at most one connection may hold
+    pub fn is_unique(&self) -> bool {
+        matches!(self, ObsConnectionRole::Recording | ObsConnectionRole::Streaming)
+    }
+
+    // "OBS_REC" -> Recording, "OBS_STR" -> Streaming, ... Whole words only,
+    // so names like "Director" or "Instructor" stay General
+    pub fn infer_from_name(name: &str) -> Self {
+        let name = name.to_uppercase();
+        let has_word = |words: &[&str]| {
+            name.split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|word| words.contains(&word))
+        };
+
+        if has_word(&["REC", "RECORD", "RECORDING"]) {
+            ObsConnectionRole::Recording
+        } else if has_word(&["STR", "STREAM", "STREAMING"]) {
+            ObsConnectionRole::Streaming
+        } else if has_word(&["REPLAY"]) {
+            ObsConnectionRole::Replay
+        } else {
+            ObsConnectionRole::General
+        }
+    }
 }
 
 // OBS Connection Status
@@ -38,14 +112,331 @@ pub enum ObsConnectionStatus {
 pub struct ObsConnection {
     pub config: ObsConnectionConfig,
     pub status: ObsConnectionStatus,
-    pub websocket: Option<tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>>,
+    // Outgoing messages, written to the socket by the connection's writer task
+    pub websocket: Option<mpsc::UnboundedSender<Message>>,
+    // Reads responses and events until the socket closes
+    pub reader_task: Option<tokio::task::JoinHandle<()>>,
     pub request_id_counter: u64,
-    pub pending_requests: HashMap<String, tokio::sync::oneshot::Sender<serde_json::Value>>,
+    pub pending_requests: HashMap<String, oneshot::Sender<serde_json::Value>>,
+    pub last_ping_ms: Option<u64>,
+    pub last_recording_path: Option<String>,
+    // Output file of the recording in progress, as reported when it started
+    pub recording_output_path: Option<String>,
+    pub last_stream_frames: Option<(u64, u64)>,
+    pub replay_buffer_active: Option<bool>,
+    pub replay_buffer_stopped_by_user: bool,
+    pub capabilities: Option<ObsCapabilities>,
+    pub recording_pause_spans: Vec<RecordingPauseSpan>,
+    pub reconnect_attempts: u32,
+    pub next_reconnect_at: Option<Instant>,
+    // Background stream health monitor, at most one per connection
+    pub stream_health_monitor: Option<tokio::task::JoinHandle<()>>,
+    // Background recording progress listener, at most one per connection
+    pub recording_progress_listener: Option<tokio::task::JoinHandle<()>>,
+}
+
+// A span during which recording was paused (unix milliseconds)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingPauseSpan {
+    pub paused_at_ms: u64,
+    pub resumed_at_ms: Option<u64>,
+}
+
+// What the connected OBS instance supports, detected at connect time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObsCapabilities {
+    pub obs_version: Option<String>,
+    pub websocket_version: Option<String>,
+    pub available_requests: Vec<String>,
+}
+
+impl ObsCapabilities {
+    pub fn supports(&self, request_type: &str) -> bool {
+        self.available_requests.iter().any(|r| r == request_type)
+    }
+}
+
+// Scene item transform (position/scale/rotation)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SceneItemTransform {
+    pub x: f64,
+    pub y: f64,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub rotation: f64,
+}
+
+impl SceneItemTransform {
+    // Keep values within ranges OBS can render sensibly
+    pub fn clamped(&self) -> Self {
+        Self {
+            x: self.x.clamp(-MAX_SCENE_ITEM_POSITION, MAX_SCENE_ITEM_POSITION),
+            y: self.y.clamp(-MAX_SCENE_ITEM_POSITION, MAX_SCENE_ITEM_POSITION),
+            scale_x: self.scale_x.clamp(MIN_SCENE_ITEM_SCALE, MAX_SCENE_ITEM_SCALE),
+            scale_y: self.scale_y.clamp(MIN_SCENE_ITEM_SCALE, MAX_SCENE_ITEM_SCALE),
+            rotation: self.rotation.rem_euclid(360.0),
+        }
+    }
+}
+
+const MAX_SCENE_ITEM_POSITION: f64 = 16384.0;
+const MIN_SCENE_ITEM_SCALE: f64 = 0.01;
+const MAX_SCENE_ITEM_SCALE: f64 = 100.0;
+
+// Ping requests should fail fast when the OBS host is unreachable
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+// How long to wait for a reply before giving up on a request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// How long each step of connecting and logging in may take
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// obs-websocket v5 RPC version we speak
+const OBS_RPC_VERSION: u64 = 1;
+
+// obs-websocket v5 close code for a failed Identify
+const CLOSE_CODE_AUTHENTICATION_FAILED: u16 = 4009;
+
+// Stream health snapshot; ratios are skipped/total output frames
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamHealth {
+    pub is_streaming: bool,
+    pub skipped_frames: u64,
+    pub total_frames: u64,
+    pub drop_ratio: f64,
+    pub recent_drop_ratio: f64,
+    pub output_bytes: Option<u64>,
+    pub congestion: Option<f64>,
+}
+
+// Warn when more than 5% of frames since the last sample were dropped
+pub const DEFAULT_STREAM_DROP_THRESHOLD: f64 = 0.05;
+
+// Background monitors never poll faster than this, whatever they are asked for
+const MIN_MONITOR_INTERVAL: Duration = Duration::from_millis(100);
+
+// Keep-alive settings for idle OBS connections. Dead connections are
+// retried with a backoff doubling from interval_ms up to max_backoff_ms.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ObsHeartbeatConfig {
+    pub interval_ms: u64,
+    pub timeout_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for ObsHeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 15_000,
+            timeout_ms: 5_000,
+            max_backoff_ms: 120_000,
+        }
+    }
+}
+
+impl ObsHeartbeatConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_ms < MIN_HEARTBEAT_INTERVAL_MS {
+            return Err(format!("Heartbeat interval must be at least {}ms", MIN_HEARTBEAT_INTERVAL_MS));
+        }
+        if self.timeout_ms == 0 || self.timeout_ms > self.interval_ms {
+            return Err("Heartbeat timeout must be positive and no longer than the interval".to_string());
+        }
+        if self.max_backoff_ms < self.interval_ms {
+            return Err("Maximum reconnect backoff must not be shorter than the interval".to_string());
+        }
+        Ok(())
+    }
+
+    // Delay before the next reconnect after `attempts` failed ones
+    pub fn reconnect_backoff(&self, attempts: u32) -> Duration {
+        let factor = 1u64 << attempts.saturating_sub(1).min(16);
+        Duration::from_millis(self.interval_ms.saturating_mul(factor).min(self.max_backoff_ms))
+    }
+}
+
+const MIN_HEARTBEAT_INTERVAL_MS: u64 = 1_000;
+
+// Named encoder settings, e.g. a higher bitrate for finals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityProfile {
+    pub name: String,
+    pub video_bitrate_kbps: u32,
+    pub encoder: String,
+    pub preset: String,
+    #[serde(default = "default_keyframe_interval_sec")]
+    pub keyframe_interval_sec: u32,
 }
 
-// OBS Plugin Manager
+fn default_keyframe_interval_sec() -> u32 {
+    SIMPLE_OUTPUT_KEYFRAME_INTERVAL_SEC
+}
+
+impl QualityProfile {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Quality profile name must not be empty".to_string());
+        }
+        if !(MIN_VIDEO_BITRATE_KBPS..=MAX_VIDEO_BITRATE_KBPS).contains(&self.video_bitrate_kbps) {
+            return Err(format!(
+                "Video bitrate must be between {} and {} kbps",
+                MIN_VIDEO_BITRATE_KBPS, MAX_VIDEO_BITRATE_KBPS
+            ));
+        }
+        if self.encoder.trim().is_empty() {
+            return Err("Encoder must not be empty".to_string());
+        }
+        if self.preset.trim().is_empty() {
+            return Err("Encoder preset must not be empty".to_string());
+        }
+        if !(1..=MAX_KEYFRAME_INTERVAL_SEC).contains(&self.keyframe_interval_sec) {
+            return Err(format!("Keyframe interval must be between 1 and {} seconds", MAX_KEYFRAME_INTERVAL_SEC));
+        }
+        Ok(())
+    }
+}
+
+const MIN_VIDEO_BITRATE_KBPS: u32 = 500;
+const MAX_VIDEO_BITRATE_KBPS: u32 = 50_000;
+const MAX_KEYFRAME_INTERVAL_SEC: u32 = 10;
+
+// OBS's simple output mode always uses 2s keyframes; only x264's custom
+// options can override it
+const SIMPLE_OUTPUT_KEYFRAME_INTERVAL_SEC: u32 = 2;
+const SIMPLE_OUTPUT_X264_ENCODER: &str = "x264";
+
+// How long to wait for OBS to finish loading a scene collection
+const SCENE_COLLECTION_LOAD_TIMEOUT: Duration = Duration::from_secs(10);
+const SCENE_COLLECTION_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// Live recording progress; elapsed time freezes while paused
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingProgress {
+    pub is_recording: bool,
+    pub is_paused: bool,
+    pub elapsed_ms: u64,
+    pub output_path: Option<String>,
+    pub output_bytes: Option<u64>,
+    pub bytes_per_sec: Option<f64>,
+}
+
+// Canvas (base) and output resolution plus frame rate
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VideoSettings {
+    pub base_width: u32,
+    pub base_height: u32,
+    pub output_width: u32,
+    pub output_height: u32,
+    pub fps_numerator: u32,
+    pub fps_denominator: u32,
+}
+
+impl VideoSettings {
+    // Reject unusable values; returns warnings for valid-but-odd settings
+    pub fn validate(&self) -> Result<Vec<String>, String> {
+        if self.base_width == 0 || self.base_height == 0 || self.output_width == 0 || self.output_height == 0 {
+            return Err("Resolutions must be positive".to_string());
+        }
+        if self.fps_denominator == 0 {
+            return Err("FPS denominator must be positive".to_string());
+        }
+
+        let fps = self.fps_numerator as f64 / self.fps_denominator as f64;
+        if !(MIN_FPS..=MAX_FPS).contains(&fps) {
+            return Err(format!("FPS {:.2} is outside the supported range {}-{}", fps, MIN_FPS, MAX_FPS));
+        }
+
+        let mut warnings = Vec::new();
+        if self.output_width > self.base_width || self.output_height > self.base_height {
+            warnings.push(format!(
+                "Output resolution {}x{} exceeds base resolution {}x{}; recordings will be upscaled",
+                self.output_width, self.output_height, self.base_width, self.base_height
+            ));
+        }
+
+        Ok(warnings)
+    }
+}
+
+const MIN_FPS: f64 = 1.0;
+const MAX_FPS: f64 = 240.0;
+
+// One selectable value of an input's list property (e.g. a capture device)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputPropertyItem {
+    pub name: String,
+    pub value: serde_json::Value,
+    pub enabled: bool,
+}
+
+// Snapshot of an OBS instance's configuration for applying to another instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObsConfigSnapshot {
+    pub obs_version: Option<String>,
+    pub scene_collection: Option<String>,
+    pub profile: Option<String>,
+    pub scenes: Vec<String>,
+    pub current_scene: Option<String>,
+    pub recording_directory: Option<String>,
+    pub studio_mode: Option<bool>,
+    #[serde(default)]
+    pub video_settings: Option<VideoSettings>,
+    #[serde(default)]
+    pub inputs: Vec<ObsInputSnapshot>,
+    // Stream service type and settings, without the stream key
+    #[serde(default)]
+    pub stream_service: Option<ObsStreamServiceSnapshot>,
+    // Parts that could not be read back, with the reason
+    #[serde(default)]
+    pub not_exported: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObsInputSnapshot {
+    pub name: String,
+    pub kind: String,
+    pub settings: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObsStreamServiceSnapshot {
+    pub service_type: String,
+    pub settings: serde_json::Value,
+}
+
+// Which parts of a snapshot were applied and which were skipped (with reason)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObsConfigImportReport {
+    pub applied: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+// Longest text we push into an OBS text source
+pub const MAX_TEXT_SOURCE_LENGTH: usize = 256;
+
+const RECORDING_INDICATOR_TEXT: &str = "● REC";
+
+// Transient connection tests should not keep the operator waiting
+const TEST_CONNECTION_TIMEOUT: Duration = Duration::from_secs(3);
+
+// Outcome of testing a connection config without saving it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObsConnectionTestResult {
+    pub obs_version: Option<String>,
+    pub websocket_version: Option<String>,
+    pub authentication_required: bool,
+}
+
+// OBS Plugin Manager (clones share the same connections)
+#[derive(Clone)]
 pub struct ObsPlugin {
     connections: Arc<Mutex<HashMap<String, ObsConnection>>>,
+    quality_profiles: Arc<Mutex<HashMap<String, QualityProfile>>>,
+    // File quality profiles are saved to, once loaded from there
+    quality_profiles_path: Arc<Mutex<Option<PathBuf>>>,
+    heartbeat_config: Arc<Mutex<ObsHeartbeatConfig>>,
     event_tx: mpsc::UnboundedSender<ObsEvent>,
 }
 
@@ -63,6 +454,7 @@ pub enum ObsEvent {
     RecordingStateChanged {
         connection_name: String,
         is_recording: bool,
+        output_path: Option<String>,
     },
     StreamStateChanged {
         connection_name: String,
@@ -76,33 +468,90 @@ pub enum ObsEvent {
         connection_name: String,
         error: String,
     },
+    RecordingProgress {
+        connection_name: String,
+        progress: RecordingProgress,
+    },
+    SceneCollectionChanged {
+        connection_name: String,
+        collection_name: String,
+    },
+    ProfileChanged {
+        connection_name: String,
+        profile_name: String,
+    },
+    StreamHealthWarning {
+        connection_name: String,
+        drop_ratio: f64,
+        threshold: f64,
+    },
+    BulkOperationProgress {
+        operation: String,
+        connection_name: String,
+        success: bool,
+        error: Option<String>,
+    },
+}
+
+// Per-connection outcome of an operation run across all connections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkOperationResult {
+    pub connection_name: String,
+    pub success: bool,
+    pub error: Option<String>,
 }
 
 impl ObsPlugin {
     pub fn new(event_tx: mpsc::UnboundedSender<ObsEvent>) -> Self {
         Self {
             connections: Arc::new(Mutex::new(HashMap::new())),
+            quality_profiles: Arc::new(Mutex::new(HashMap::new())),
+            quality_profiles_path: Arc::new(Mutex::new(None)),
+            heartbeat_config: Arc::new(Mutex::new(ObsHeartbeatConfig::default())),
             event_tx,
         }
     }
 
     // Add a new OBS connection
     pub async fn add_connection(&self, config: ObsConnectionConfig) -> Result<(), String> {
-        let mut connections = self.connections.lock().unwrap();
-        
-        if connections.contains_key(&config.name) {
-            return Err(format!("Connection '{}' already exists", config.name));
-        }
+        // Released before connecting, which locks again
+        {
+            let mut connections = self.connections.lock().unwrap();
 
-        let connection = ObsConnection {
-            config: config.clone(),
-            status: ObsConnectionStatus::Disconnected,
-            websocket: None,
-            request_id_counter: 0,
-            pending_requests: HashMap::new(),
-        };
+            if connections.contains_key(&config.name) {
+                return Err(format!("Connection '{}' already exists", config.name));
+            }
 
-        connections.insert(config.name.clone(), connection);
+            // Only explicit roles are exclusive; inferred ones yield to them
+            if let Some(role) = config.role.filter(|role| role.is_unique()) {
+                if let Some(existing) = connections.values().find(|c| c.config.role == Some(role)) {
+                    return Err(format!("Connection '{}' already has the {:?} role", existing.config.name, role));
+                }
+            }
+
+            let connection = ObsConnection {
+                config: config.clone(),
+                status: ObsConnectionStatus::Disconnected,
+                websocket: None,
+                reader_task: None,
+                request_id_counter: 0,
+                pending_requests: HashMap::new(),
+                last_ping_ms: None,
+                last_recording_path: None,
+                recording_output_path: None,
+                last_stream_frames: None,
+                replay_buffer_active: None,
+                replay_buffer_stopped_by_user: false,
+                capabilities: None,
+                recording_pause_spans: Vec::new(),
+                stream_health_monitor: None,
+                recording_progress_listener: None,
+                reconnect_attempts: 0,
+                next_reconnect_at: None,
+            };
+
+            connections.insert(config.name.clone(), connection);
+        }
 
         // Start connection if enabled
         if config.enabled {
@@ -114,28 +563,123 @@ impl ObsPlugin {
 
     // Connect to OBS instance
     pub async fn connect_obs(&self, connection_name: &str) -> Result<(), String> {
-        // Get connection config first
+        // Get connection config and drop any previous socket
         let config = {
-            let connections = self.connections.lock().unwrap();
-            let connection = connections.get(connection_name)
+            let mut connections = self.connections.lock().unwrap();
+            let connection = connections.get_mut(connection_name)
                 .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+
+            if let Some(reader_task) = connection.reader_task.take() {
+                reader_task.abort();
+            }
+            connection.websocket = None;
+            connection.pending_requests.clear();
+            connection.status = ObsConnectionStatus::Connecting;
             connection.config.clone()
         };
 
-        // Update status to connecting
+        // Send status change event
+        let _ = self.event_tx.send(ObsEvent::ConnectionStatusChanged {
+            connection_name: connection_name.to_string(),
+            status: ObsConnectionStatus::Connecting,
+        });
+
+        if let Err(e) = self.open_connection(connection_name, &config).await {
+            let status = ObsConnectionStatus::Error(e.clone());
+            {
+                let mut connections = self.connections.lock().unwrap();
+                if let Some(connection) = connections.get_mut(connection_name) {
+                    connection.status = status.clone();
+                }
+            }
+
+            let _ = self.event_tx.send(ObsEvent::ConnectionStatusChanged {
+                connection_name: connection_name.to_string(),
+                status,
+            });
+            return Err(e);
+        }
+
+        if let Err(e) = self.detect_capabilities(connection_name).await {
+            let _ = self.event_tx.send(ObsEvent::Error {
+                connection_name: connection_name.to_string(),
+                error: format!("Failed to detect OBS capabilities: {}", e),
+            });
+        }
+
+        if config.keeps_replay_buffer_running() && self.supports_request(connection_name, "StartReplayBuffer") {
+            if let Err(e) = self.start_replay_buffer(connection_name).await {
+                let _ = self.event_tx.send(ObsEvent::Error {
+                    connection_name: connection_name.to_string(),
+                    error: format!("Failed to auto-start replay buffer: {}", e),
+                });
+            }
+        }
+
+        // Switch to the scene collection assigned to this connection
+        if let Some(collection_name) = &config.scene_collection {
+            if let Err(e) = self.set_scene_collection(connection_name, collection_name).await {
+                let _ = self.event_tx.send(ObsEvent::Error {
+                    connection_name: connection_name.to_string(),
+                    error: format!("Failed to switch to scene collection '{}': {}", collection_name, e),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Try a transient connection without touching the connection map
+    pub async fn test_connection_config(
+        &self,
+        host: &str,
+        port: u16,
+        password: Option<&str>,
+        protocol_version: ObsWebSocketVersion,
+    ) -> Result<ObsConnectionTestResult, String> {
+        let ws_url = format!("ws://{}:{}/", host, port);
+
+        let (mut ws_stream, _) = tokio::time::timeout(TEST_CONNECTION_TIMEOUT, tokio_tungstenite::connect_async(&ws_url))
+            .await
+            .map_err(|_| format!("Timed out connecting to {}:{}", host, port))?
+            .map_err(|e| connect_error(host, port, e))?;
+
+        // Log in the same way connect_obs does, so a wrong password fails here
+        let result = match protocol_version {
+            ObsWebSocketVersion::V4 => handshake_v4(&mut ws_stream, password).await,
+            ObsWebSocketVersion::V5 => handshake_v5(&mut ws_stream, password).await,
+        };
+
+        let _ = ws_stream.close(None).await;
+
+        result.map_err(|e| format!("OBS at {}:{}: {}", host, port, e))
+    }
+
+    // Explicitly disconnect; heartbeats don't reconnect disconnected connections
+    pub fn disconnect_obs(&self, connection_name: &str) -> Result<(), String> {
         {
             let mut connections = self.connections.lock().unwrap();
-            if let Some(connection) = connections.get_mut(connection_name) {
-                connection.status = ObsConnectionStatus::Connecting;
+            let connection = connections.get_mut(connection_name)
+                .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+
+            if let Some(reader_task) = connection.reader_task.take() {
+                reader_task.abort();
             }
+            connection.websocket = None;
+            connection.pending_requests.clear();
+            connection.status = ObsConnectionStatus::Disconnected;
         }
 
-        // Send status change event
         let _ = self.event_tx.send(ObsEvent::ConnectionStatusChanged {
             connection_name: connection_name.to_string(),
-            status: ObsConnectionStatus::Connecting,
+            status: ObsConnectionStatus::Disconnected,
         });
 
+        Ok(())
+    }
+
+    // Open the socket, log in and start the reader/writer tasks
+    async fn open_connection(&self, connection_name: &str, config: &ObsConnectionConfig) -> Result<(), String> {
         // Build WebSocket URL
         let ws_url = format!(
             "ws://{}:{}/",
@@ -144,50 +688,50 @@ impl ObsPlugin {
         );
 
         // Connect to WebSocket
-        let (ws_stream, _) = tokio_tungstenite::connect_async(&ws_url)
+        let (mut ws_stream, _) = tokio::time::timeout(HANDSHAKE_TIMEOUT, tokio_tungstenite::connect_async(&ws_url))
             .await
-            .map_err(|e| format!("Failed to connect to OBS: {}", e))?;
+            .map_err(|_| format!("Timed out connecting to {}:{}", config.host, config.port))?
+            .map_err(|e| connect_error(&config.host, config.port, e))?;
 
-        // Update connection
-        let mut connections = self.connections.lock().unwrap();
-        let connection = connections.get_mut(connection_name).unwrap();
-        connection.websocket = Some(ws_stream);
-        connection.status = ObsConnectionStatus::Connected;
+        self.set_connection_status(connection_name, ObsConnectionStatus::Authenticating);
 
         // Handle protocol-specific authentication
+        let password = config.password.as_deref();
         match config.protocol_version {
-            ObsWebSocketVersion::V4 => {
-                self.authenticate_v4(connection_name).await?;
-            }
-            ObsWebSocketVersion::V5 => {
-                self.authenticate_v5(connection_name).await?;
-            }
-        }
+            ObsWebSocketVersion::V4 => handshake_v4(&mut ws_stream, password).await?,
+            ObsWebSocketVersion::V5 => handshake_v5(&mut ws_stream, password).await?,
+        };
 
-        Ok(())
-    }
+        let (mut sink, stream) = ws_stream.split();
+        let (websocket_tx, mut websocket_rx) = mpsc::unbounded_channel::<Message>();
 
-    // Authenticate using OBS WebSocket v4 protocol
-    async fn authenticate_v4(&self, connection_name: &str) -> Result<(), String> {
-        let mut connections = self.connections.lock().unwrap();
-        let connection = connections.get_mut(connection_name).unwrap();
-        
-        connection.status = ObsConnectionStatus::Authenticating;
+        // Writer ends when the connection's sender is dropped
+        tokio::spawn(async move {
+            while let Some(message) = websocket_rx.recv().await {
+                if sink.send(message).await.is_err() {
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
 
-        // V4 authentication is simpler - just send password if required
-        if let Some(_password) = &connection.config.password {
-            let _auth_request = serde_json::json!({
-                "request-type": "GetAuthRequired",
-                "message-id": self.generate_request_id(connection)
-            });
+        let reader_task = tokio::spawn(self.clone().read_messages(
+            connection_name.to_string(),
+            config.protocol_version,
+            stream,
+        ));
 
-            // Send authentication request
-            // Implementation would send the request and handle response
+        {
+            let mut connections = self.connections.lock().unwrap();
+            let connection = connections.get_mut(connection_name)
+                .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+            connection.websocket = Some(websocket_tx);
+            connection.reader_task = Some(reader_task);
+            connection.status = ObsConnectionStatus::Authenticated;
+            connection.reconnect_attempts = 0;
+            connection.next_reconnect_at = None;
         }
 
-        connection.status = ObsConnectionStatus::Authenticated;
-        drop(connections);
-
         // Send status change event
         let _ = self.event_tx.send(ObsEvent::ConnectionStatusChanged {
             connection_name: connection_name.to_string(),
@@ -197,31 +741,139 @@ impl ObsPlugin {
         Ok(())
     }
 
-    // Authenticate using OBS WebSocket v5 protocol
-    async fn authenticate_v5(&self, connection_name: &str) -> Result<(), String> {
+    fn set_connection_status(&self, connection_name: &str, status: ObsConnectionStatus) {
         let mut connections = self.connections.lock().unwrap();
-        let connection = connections.get_mut(connection_name).unwrap();
-        
-        connection.status = ObsConnectionStatus::Authenticating;
+        if let Some(connection) = connections.get_mut(connection_name) {
+            connection.status = status;
+        }
+    }
 
-        // V5 uses a more complex authentication flow
-        // 1. Wait for Hello message
-        // 2. Send Identify with authentication if required
-        // 3. Wait for Identified message
+    // Route responses to their waiting requests and events to handlers
+    async fn read_messages(
+        self,
+        connection_name: String,
+        protocol_version: ObsWebSocketVersion,
+        mut stream: SplitStream<ObsWebSocketStream>,
+    ) {
+        while let Some(Ok(message)) = stream.next().await {
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let Ok(message) = serde_json::from_str::<serde_json::Value>(&text) else {
+                continue;
+            };
 
-        // For now, we'll implement a basic version
-        // In a full implementation, you'd handle the full v5 authentication flow
+            match parse_obs_message(protocol_version, message) {
+                ObsMessage::Response { request_id, response } => {
+                    let response_tx = {
+                        let mut connections = self.connections.lock().unwrap();
+                        connections.get_mut(&connection_name)
+                            .and_then(|c| c.pending_requests.remove(&request_id))
+                    };
+                    if let Some(response_tx) = response_tx {
+                        let _ = response_tx.send(response);
+                    }
+                }
+                ObsMessage::Event { event_type, data } => {
+                    self.handle_obs_event(&connection_name, &event_type, &data);
+                }
+                ObsMessage::Other => {}
+            }
+        }
 
-        connection.status = ObsConnectionStatus::Authenticated;
-        drop(connections);
+        // Socket closed; fail waiting requests and let the heartbeat reconnect
+        let status = ObsConnectionStatus::Error("Connection to OBS lost".to_string());
+        let lost = {
+            let mut connections = self.connections.lock().unwrap();
+            match connections.get_mut(&connection_name) {
+                Some(connection) if connection.status == ObsConnectionStatus::Authenticated => {
+                    connection.websocket = None;
+                    connection.reader_task = None;
+                    connection.pending_requests.clear();
+                    connection.status = status.clone();
+                    true
+                }
+                _ => false,
+            }
+        };
 
-        // Send status change event
-        let _ = self.event_tx.send(ObsEvent::ConnectionStatusChanged {
-            connection_name: connection_name.to_string(),
-            status: ObsConnectionStatus::Authenticated,
-        });
+        if lost {
+            let _ = self.event_tx.send(ObsEvent::ConnectionStatusChanged {
+                connection_name,
+                status,
+            });
+        }
+    }
 
-        Ok(())
+    // Handle an event pushed by OBS. Runs on the reader task, so anything
+    // that sends requests must be spawned rather than awaited here.
+    fn handle_obs_event(&self, connection_name: &str, event_type: &str, data: &serde_json::Value) {
+        match event_type {
+            // v4 / v5
+            "SwitchScenes" | "CurrentProgramSceneChanged" => {
+                let scene_name = data["scene-name"].as_str().or_else(|| data["sceneName"].as_str());
+                if let Some(scene_name) = scene_name {
+                    let _ = self.event_tx.send(ObsEvent::SceneChanged {
+                        connection_name: connection_name.to_string(),
+                        scene_name: scene_name.to_string(),
+                    });
+                }
+            }
+            "StreamStarted" | "StreamStopped" => {
+                let _ = self.event_tx.send(ObsEvent::StreamStateChanged {
+                    connection_name: connection_name.to_string(),
+                    is_streaming: event_type == "StreamStarted",
+                });
+            }
+            "StreamStateChanged" => {
+                if let Some(is_streaming) = output_state_settled(data) {
+                    let _ = self.event_tx.send(ObsEvent::StreamStateChanged {
+                        connection_name: connection_name.to_string(),
+                        is_streaming,
+                    });
+                }
+            }
+            // v4 / v5
+            "RecordingStarted" | "RecordingStopped" => {
+                let output_path = data["recordingFilename"].as_str().map(|s| s.to_string());
+                self.handle_record_state_event(connection_name, event_type == "RecordingStarted", output_path);
+            }
+            "RecordStateChanged" => {
+                if let Some(is_recording) = output_state_settled(data) {
+                    let output_path = data["outputPath"].as_str().map(|s| s.to_string());
+                    self.handle_record_state_event(connection_name, is_recording, output_path);
+                }
+            }
+            // v4 / v5. Restarting sends requests, so it can't block the reader.
+            "ReplayStarted" | "ReplayStopped" | "ReplayBufferStateChanged" => {
+                let is_active = match event_type {
+                    "ReplayBufferStateChanged" => output_state_settled(data),
+                    _ => Some(event_type == "ReplayStarted"),
+                };
+                if let Some(is_active) = is_active {
+                    let plugin = self.clone();
+                    let connection_name = connection_name.to_string();
+                    tokio::spawn(async move {
+                        plugin.handle_replay_buffer_state_changed(&connection_name, is_active).await;
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Recording started or stopped, from our own request or inside OBS.
+    // Updating the indicator sends requests, so it can't block the reader.
+    fn handle_record_state_event(&self, connection_name: &str, is_recording: bool, output_path: Option<String>) {
+        self.set_recording_output_path(connection_name, is_recording, output_path);
+
+        let plugin = self.clone();
+        let connection_name = connection_name.to_string();
+        tokio::spawn(async move {
+            plugin.update_recording_indicator(&connection_name, is_recording).await;
+        });
     }
 
     // Send request to OBS (protocol-agnostic)
@@ -231,49 +883,76 @@ impl ObsPlugin {
         request_type: &str,
         request_data: Option<serde_json::Value>,
     ) -> Result<serde_json::Value, String> {
-        let mut connections = self.connections.lock().unwrap();
-        let connection = connections.get_mut(connection_name)
-            .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
-
-        if connection.status != ObsConnectionStatus::Authenticated {
-            return Err("OBS connection not authenticated".to_string());
-        }
+        self.send_request_with_timeout(connection_name, request_type, request_data, REQUEST_TIMEOUT).await
+    }
 
-        let request_id = self.generate_request_id(connection);
-        let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+    async fn send_request_with_timeout(
+        &self,
+        connection_name: &str,
+        request_type: &str,
+        request_data: Option<serde_json::Value>,
+        timeout: Duration,
+    ) -> Result<serde_json::Value, String> {
+        // Hold the lock only while registering the request so other requests
+        // aren't blocked while we wait for the response
+        let (request_id, protocol_version, response_rx) = {
+            let mut connections = self.connections.lock().unwrap();
+            let connection = connections.get_mut(connection_name)
+                .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
 
-        // Store pending request
-        connection.pending_requests.insert(request_id.clone(), response_tx);
+            if connection.status != ObsConnectionStatus::Authenticated {
+                return Err("OBS connection not authenticated".to_string());
+            }
 
-        // Create request based on protocol version
-        let request = match connection.config.protocol_version {
-            ObsWebSocketVersion::V4 => {
-                serde_json::json!({
-                    "request-type": request_type,
-                    "message-id": request_id,
-                    "request-data": request_data
-                })
+            // Fail clearly instead of sending a request this OBS doesn't know
+            if let Some(capabilities) = &connection.capabilities {
+                if !capabilities.supports(request_type) {
+                    return Err(format!(
+                        "'{}' is not supported on this OBS version (OBS {}, obs-websocket {})",
+                        request_type,
+                        capabilities.obs_version.as_deref().unwrap_or("unknown"),
+                        capabilities.websocket_version.as_deref().unwrap_or("unknown")
+                    ));
+                }
             }
-            ObsWebSocketVersion::V5 => {
-                serde_json::json!({
-                    "op": 6, // Request opcode
-                    "d": {
-                        "requestType": request_type,
-                        "requestId": request_id,
-                        "requestData": request_data
-                    }
-                })
-            }
-        };
 
-        // Send request via WebSocket
-        // Implementation would send the request through the WebSocket connection
+            let request_id = self.generate_request_id(connection);
+            let protocol_version = connection.config.protocol_version;
+            let request = build_request(protocol_version, &request_id, request_type, request_data);
+
+            // Send request via WebSocket
+            let websocket = connection.websocket.as_ref()
+                .ok_or_else(|| "OBS connection lost".to_string())?;
+            websocket.send(Message::Text(request.to_string()))
+                .map_err(|_| "OBS connection lost".to_string())?;
+
+            // Store pending request
+            let (response_tx, response_rx) = oneshot::channel();
+            connection.pending_requests.insert(request_id.clone(), response_tx);
+
+            (request_id, protocol_version, response_rx)
+        };
 
         // Wait for response
-        let response = response_rx.await
-            .map_err(|_| "Request timeout or connection lost".to_string())?;
+        let response = match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => return Err("OBS connection lost".to_string()),
+            Err(_) => {
+                // Nobody will answer this one; don't keep its sender around
+                let mut connections = self.connections.lock().unwrap();
+                if let Some(connection) = connections.get_mut(connection_name) {
+                    connection.pending_requests.remove(&request_id);
+                }
+                return Err(format!(
+                    "OBS connection '{}' did not respond to {} within {}ms",
+                    connection_name,
+                    request_type,
+                    timeout.as_millis()
+                ));
+            }
+        };
 
-        Ok(response)
+        request_result(protocol_version, request_type, response)
     }
 
     // Get current scene
@@ -327,28 +1006,328 @@ impl ObsPlugin {
 
     // Start recording
     pub async fn start_recording(&self, connection_name: &str) -> Result<(), String> {
-        self.send_request(connection_name, "StartRecording", None).await?;
+        let request_type = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => "StartRecording",
+            ObsWebSocketVersion::V5 => "StartRecord",
+        };
+        self.send_request(connection_name, request_type, None).await?;
+
+        {
+            let mut connections = self.connections.lock().unwrap();
+            if let Some(connection) = connections.get_mut(connection_name) {
+                connection.recording_pause_spans.clear();
+            }
+        }
+
+        Ok(())
+    }
+
+    // Pause recording
+    pub async fn pause_recording(&self, connection_name: &str) -> Result<(), String> {
+        let request_type = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => "PauseRecording",
+            ObsWebSocketVersion::V5 => "PauseRecord",
+        };
+        self.send_request(connection_name, request_type, None).await?;
+
+        let mut connections = self.connections.lock().unwrap();
+        if let Some(connection) = connections.get_mut(connection_name) {
+            connection.recording_pause_spans.push(RecordingPauseSpan {
+                paused_at_ms: unix_time_ms(),
+                resumed_at_ms: None,
+            });
+        }
+
+        Ok(())
+    }
+
+    // Resume recording
+    pub async fn resume_recording(&self, connection_name: &str) -> Result<(), String> {
+        let request_type = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => "ResumeRecording",
+            ObsWebSocketVersion::V5 => "ResumeRecord",
+        };
+        self.send_request(connection_name, request_type, None).await?;
+
+        let mut connections = self.connections.lock().unwrap();
+        if let Some(span) = connections.get_mut(connection_name)
+            .and_then(|c| c.recording_pause_spans.last_mut())
+            .filter(|span| span.resumed_at_ms.is_none())
+        {
+            span.resumed_at_ms = Some(unix_time_ms());
+        }
+
         Ok(())
     }
 
-    // Stop recording
-    pub async fn stop_recording(&self, connection_name: &str) -> Result<(), String> {
-        self.send_request(connection_name, "StopRecording", None).await?;
+    // Handle a match break on the connection holding the Recording role
+    pub async fn handle_match_break_for_recording(&self, in_break: bool) -> Result<bool, String> {
+        let connection_name = self.get_connection_for_role(ObsConnectionRole::Recording)?;
+        self.handle_match_break(&connection_name, in_break).await
+    }
+
+    // Handle a match break starting/ending when pause_recording_on_break is set.
+    // Returns whether recording was paused or resumed.
+    pub async fn handle_match_break(&self, connection_name: &str, in_break: bool) -> Result<bool, String> {
+        let (enabled, is_paused) = {
+            let connections = self.connections.lock().unwrap();
+            let connection = connections.get(connection_name)
+                .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+            let is_paused = connection.recording_pause_spans.last()
+                .map(|span| span.resumed_at_ms.is_none())
+                .unwrap_or(false);
+            (connection.config.pause_recording_on_break, is_paused)
+        };
+
+        if !enabled || in_break == is_paused {
+            return Ok(false);
+        }
+
+        let request_type = match (self.get_protocol_version(connection_name)?, in_break) {
+            (ObsWebSocketVersion::V4, true) => "PauseRecording",
+            (ObsWebSocketVersion::V4, false) => "ResumeRecording",
+            (ObsWebSocketVersion::V5, true) => "PauseRecord",
+            (ObsWebSocketVersion::V5, false) => "ResumeRecord",
+        };
+        if !self.supports_request(connection_name, request_type) {
+            return Ok(false);
+        }
+
+        // Only pause an active recording
+        if in_break && !self.get_recording_status(connection_name).await? {
+            return Ok(false);
+        }
+
+        if in_break {
+            self.pause_recording(connection_name).await?;
+        } else {
+            self.resume_recording(connection_name).await?;
+        }
+
+        Ok(true)
+    }
+
+    // Pause spans of the current recording
+    pub fn get_recording_pause_spans(&self, connection_name: &str) -> Vec<RecordingPauseSpan> {
+        let connections = self.connections.lock().unwrap();
+        connections.get(connection_name)
+            .map(|c| c.recording_pause_spans.clone())
+            .unwrap_or_default()
+    }
+
+    // Stop recording, returning the output file OBS wrote
+    pub async fn stop_recording(&self, connection_name: &str) -> Result<Option<String>, String> {
+        let request_type = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => "StopRecording",
+            ObsWebSocketVersion::V5 => "StopRecord",
+        };
+        let response = self.send_request(connection_name, request_type, None).await?;
+
+        // v5 reports the output path in the stop response; v4 only does so
+        // in the RecordingStopped event, which may not have arrived yet
+        let reported_path = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => None,
+            ObsWebSocketVersion::V5 => response["outputPath"].as_str().map(|s| s.to_string()),
+        };
+
+        // Best effort: OBS has already stopped, so a failed lookup mustn't
+        // skip the bookkeeping below
+        let output_path = match reported_path.or_else(|| self.get_recording_output_path(connection_name)) {
+            Some(path) => Some(path),
+            None => self.find_newest_recording(connection_name).await.ok().flatten(),
+        };
+
+        self.handle_recording_stopped(connection_name, output_path.clone());
+
+        Ok(output_path)
+    }
+
+    // Record the output path reported by OBS when a recording stops
+    pub fn handle_recording_stopped(&self, connection_name: &str, output_path: Option<String>) {
+        {
+            let mut connections = self.connections.lock().unwrap();
+            if let Some(connection) = connections.get_mut(connection_name) {
+                connection.recording_output_path = None;
+                if output_path.is_some() {
+                    connection.last_recording_path = output_path.clone();
+                }
+            }
+        }
+
+        let _ = self.event_tx.send(ObsEvent::RecordingStateChanged {
+            connection_name: connection_name.to_string(),
+            is_recording: false,
+            output_path,
+        });
+    }
+
+    // Track the output file from OBS's record start/stop events
+    fn set_recording_output_path(&self, connection_name: &str, is_recording: bool, output_path: Option<String>) {
+        let mut connections = self.connections.lock().unwrap();
+        if let Some(connection) = connections.get_mut(connection_name) {
+            if is_recording {
+                connection.recording_output_path = output_path;
+            } else if output_path.is_some() {
+                connection.recording_output_path = None;
+                connection.last_recording_path = output_path;
+            }
+        }
+    }
+
+    // Output file of the recording in progress, if OBS reported it
+    fn get_recording_output_path(&self, connection_name: &str) -> Option<String> {
+        let connections = self.connections.lock().unwrap();
+        connections.get(connection_name).and_then(|c| c.recording_output_path.clone())
+    }
+
+    // Get the output path of the last finished recording
+    pub fn get_last_recording_path(&self, connection_name: &str) -> Option<String> {
+        let connections = self.connections.lock().unwrap();
+        connections.get(connection_name).and_then(|c| c.last_recording_path.clone())
+    }
+
+    // Get the directory OBS records into
+    pub async fn get_recording_directory(&self, connection_name: &str) -> Result<String, String> {
+        let request_type = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => "GetRecordingFolder",
+            ObsWebSocketVersion::V5 => "GetRecordDirectory",
+        };
+
+        let response = self.send_request(connection_name, request_type, None).await?;
+
+        let field = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => "rec-folder",
+            ObsWebSocketVersion::V5 => "recordDirectory",
+        };
+
+        response[field]
+            .as_str()
+            .ok_or_else(|| "Invalid response format".to_string())
+            .map(|s| s.to_string())
+    }
+
+    // Set the directory OBS records into. The path can only be checked here
+    // when OBS runs on this machine; a remote OBS reports bad paths itself.
+    pub async fn set_recording_directory(&self, connection_name: &str, directory: &str) -> Result<String, String> {
+        let directory = directory.trim();
+        if directory.is_empty() {
+            return Err("Recording path must not be empty".to_string());
+        }
+
+        let directory = if self.is_local_connection(connection_name)? {
+            validate_recording_directory(Path::new(directory))?
+                .to_string_lossy()
+                .to_string()
+        } else {
+            directory.to_string()
+        };
+
+        self.send_recording_directory(connection_name, &directory).await?;
+        Ok(directory)
+    }
+
+    async fn send_recording_directory(&self, connection_name: &str, directory: &str) -> Result<(), String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                self.send_request(connection_name, "SetRecordingFolder", Some(serde_json::json!({
+                    "rec-folder": directory
+                }))).await?;
+            }
+            ObsWebSocketVersion::V5 => {
+                self.send_request(connection_name, "SetRecordDirectory", Some(serde_json::json!({
+                    "recordDirectory": directory
+                }))).await?;
+            }
+        }
+
         Ok(())
     }
 
+    // Fall back to the most recently modified file in the record directory.
+    // Only possible when OBS records onto this machine.
+    async fn find_newest_recording(&self, connection_name: &str) -> Result<Option<String>, String> {
+        if !self.is_local_connection(connection_name)? {
+            return Ok(None);
+        }
+
+        let directory = self.get_recording_directory(connection_name).await?;
+        Ok(newest_file_in(Path::new(&directory)))
+    }
+
     // Start replay buffer
     pub async fn start_replay_buffer(&self, connection_name: &str) -> Result<(), String> {
         self.send_request(connection_name, "StartReplayBuffer", None).await?;
+        self.set_replay_buffer_state(connection_name, true, false);
         Ok(())
     }
 
-    // Stop replay buffer
+    // Stop replay buffer (an explicit stop disables auto-restart until the next start)
     pub async fn stop_replay_buffer(&self, connection_name: &str) -> Result<(), String> {
-        self.send_request(connection_name, "StopReplayBuffer", None).await?;
+        // Flag the stop up front: OBS's stopped event can beat the response,
+        // and must not be mistaken for an unexpected stop
+        let previous = self.set_replay_buffer_stopped_by_user(connection_name, true);
+        if let Err(e) = self.send_request(connection_name, "StopReplayBuffer", None).await {
+            self.set_replay_buffer_stopped_by_user(connection_name, previous);
+            return Err(e);
+        }
+
+        self.set_replay_buffer_state(connection_name, false, true);
         Ok(())
     }
 
+    // Handle a replay buffer state change reported by OBS, restarting the
+    // buffer if it stopped without the user asking for it
+    pub async fn handle_replay_buffer_state_changed(&self, connection_name: &str, is_active: bool) {
+        let should_restart = {
+            let mut connections = self.connections.lock().unwrap();
+            match connections.get_mut(connection_name) {
+                Some(connection) => {
+                    connection.replay_buffer_active = Some(is_active);
+                    !is_active
+                        && connection.config.keeps_replay_buffer_running()
+                        && !connection.replay_buffer_stopped_by_user
+                }
+                None => false,
+            }
+        };
+
+        let _ = self.event_tx.send(ObsEvent::ReplayBufferStateChanged {
+            connection_name: connection_name.to_string(),
+            is_active,
+        });
+
+        if should_restart {
+            if let Err(e) = self.start_replay_buffer(connection_name).await {
+                let _ = self.event_tx.send(ObsEvent::Error {
+                    connection_name: connection_name.to_string(),
+                    error: format!("Failed to restart replay buffer: {}", e),
+                });
+            }
+        }
+    }
+
+    // Last known replay buffer state
+    pub fn get_replay_buffer_state(&self, connection_name: &str) -> Option<bool> {
+        let connections = self.connections.lock().unwrap();
+        connections.get(connection_name).and_then(|c| c.replay_buffer_active)
+    }
+
+    // Returns the previous value
+    fn set_replay_buffer_stopped_by_user(&self, connection_name: &str, stopped_by_user: bool) -> bool {
+        let mut connections = self.connections.lock().unwrap();
+        connections.get_mut(connection_name)
+            .map(|connection| std::mem::replace(&mut connection.replay_buffer_stopped_by_user, stopped_by_user))
+            .unwrap_or(false)
+    }
+
+    fn set_replay_buffer_state(&self, connection_name: &str, is_active: bool, stopped_by_user: bool) {
+        let mut connections = self.connections.lock().unwrap();
+        if let Some(connection) = connections.get_mut(connection_name) {
+            connection.replay_buffer_active = Some(is_active);
+            connection.replay_buffer_stopped_by_user = stopped_by_user;
+        }
+    }
+
     // Save replay buffer
     pub async fn save_replay_buffer(&self, connection_name: &str) -> Result<(), String> {
         self.send_request(connection_name, "SaveReplayBuffer", None).await?;
@@ -357,11 +1336,15 @@ impl ObsPlugin {
 
     // Get recording status
     pub async fn get_recording_status(&self, connection_name: &str) -> Result<bool, String> {
-        let response = self.send_request(connection_name, "GetRecordingStatus", None).await?;
+        let request_type = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => "GetRecordingStatus",
+            ObsWebSocketVersion::V5 => "GetRecordStatus",
+        };
+        let response = self.send_request(connection_name, request_type, None).await?;
         
         match self.get_protocol_version(connection_name)? {
             ObsWebSocketVersion::V4 => {
-                Ok(response["is-recording"].as_bool().unwrap_or(false))
+                Ok(response["isRecording"].as_bool().unwrap_or(false))
             }
             ObsWebSocketVersion::V5 => {
                 Ok(response["outputActive"].as_bool().unwrap_or(false))
@@ -369,13 +1352,89 @@ impl ObsPlugin {
         }
     }
 
+    // Get recording elapsed time, output path and size
+    pub async fn get_recording_progress(&self, connection_name: &str) -> Result<RecordingProgress, String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "GetRecordingStatus", None).await?;
+                let output_path = response["recordingFilename"].as_str().map(|s| s.to_string());
+                // v4 doesn't report size; read it from disk when OBS runs locally
+                let output_bytes = match output_path.as_ref() {
+                    Some(path) if self.is_local_connection(connection_name)? => {
+                        std::fs::metadata(path).ok().map(|metadata| metadata.len())
+                    }
+                    _ => None,
+                };
+
+                Ok(RecordingProgress {
+                    is_recording: response["isRecording"].as_bool().unwrap_or(false),
+                    is_paused: response["isRecordingPaused"].as_bool().unwrap_or(false),
+                    elapsed_ms: response["recordTimecode"].as_str().and_then(parse_timecode_ms).unwrap_or(0),
+                    output_path,
+                    output_bytes,
+                    bytes_per_sec: None,
+                })
+            }
+            ObsWebSocketVersion::V5 => {
+                let response = self.send_request(connection_name, "GetRecordStatus", None).await?;
+                let is_recording = response["outputActive"].as_bool().unwrap_or(false);
+                // GetRecordStatus has no path; use the one from the record events
+                let output_path = if is_recording {
+                    self.get_recording_output_path(connection_name)
+                } else {
+                    self.get_last_recording_path(connection_name)
+                };
+
+                Ok(RecordingProgress {
+                    is_recording,
+                    is_paused: response["outputPaused"].as_bool().unwrap_or(false),
+                    elapsed_ms: response["outputDuration"].as_u64().unwrap_or(0),
+                    output_path,
+                    output_bytes: response["outputBytes"].as_u64(),
+                    bytes_per_sec: None,
+                })
+            }
+        }
+    }
+
+    // Emit recording progress events periodically until recording stops
+    pub async fn run_recording_progress_listener(&self, connection_name: &str, interval: Duration) -> Result<(), String> {
+        let interval = interval.max(MIN_MONITOR_INTERVAL);
+        let mut previous: Option<(Instant, u64)> = None;
+
+        loop {
+            let mut progress = self.get_recording_progress(connection_name).await?;
+            if !progress.is_recording {
+                break;
+            }
+
+            let now = Instant::now();
+            if let (Some((sampled_at, previous_bytes)), Some(bytes)) = (previous, progress.output_bytes) {
+                let seconds = now.duration_since(sampled_at).as_secs_f64();
+                if seconds > 0.0 {
+                    progress.bytes_per_sec = Some(bytes.saturating_sub(previous_bytes) as f64 / seconds);
+                }
+            }
+            previous = progress.output_bytes.map(|bytes| (now, bytes));
+
+            let _ = self.event_tx.send(ObsEvent::RecordingProgress {
+                connection_name: connection_name.to_string(),
+                progress,
+            });
+
+            tokio::time::sleep(interval).await;
+        }
+
+        Ok(())
+    }
+
     // Get replay buffer status
     pub async fn get_replay_buffer_status(&self, connection_name: &str) -> Result<bool, String> {
         let response = self.send_request(connection_name, "GetReplayBufferStatus", None).await?;
         
         match self.get_protocol_version(connection_name)? {
             ObsWebSocketVersion::V4 => {
-                Ok(response["is-replay-buffer-active"].as_bool().unwrap_or(false))
+                Ok(response["isReplayBufferActive"].as_bool().unwrap_or(false))
             }
             ObsWebSocketVersion::V5 => {
                 Ok(response["outputActive"].as_bool().unwrap_or(false))
@@ -383,72 +1442,3836 @@ impl ObsPlugin {
         }
     }
 
-    // Get all scenes
-    pub async fn get_scenes(&self, connection_name: &str) -> Result<Vec<String>, String> {
-        let response = self.send_request(connection_name, "GetSceneList", None).await?;
-        
-        match self.get_protocol_version(connection_name)? {
+    // Sample stream output stats and compute drop ratios. Read-only: the
+    // recent ratio is measured against the monitor's last sample.
+    pub async fn get_stream_health(&self, connection_name: &str) -> Result<StreamHealth, String> {
+        self.sample_stream_health(connection_name, false).await
+    }
+
+    async fn sample_stream_health(&self, connection_name: &str, update_baseline: bool) -> Result<StreamHealth, String> {
+        let (is_streaming, skipped_frames, total_frames, output_bytes, congestion) = match self.get_protocol_version(connection_name)? {
             ObsWebSocketVersion::V4 => {
-                let scenes = response["scenes"].as_array()
-                    .ok_or_else(|| "Invalid response format".to_string())?;
-                
-                Ok(scenes.iter()
-                    .filter_map(|scene| scene["scene-name"].as_str())
-                    .map(|s| s.to_string())
-                    .collect())
+                let status = self.send_request(connection_name, "GetStreamingStatus", None).await?;
+                let response = self.send_request(connection_name, "GetStats", None).await?;
+                let stats = &response["stats"];
+
+                (
+                    status["streaming"].as_bool().unwrap_or(false),
+                    stats["output-skipped-frames"].as_u64().unwrap_or(0),
+                    stats["output-total-frames"].as_u64().unwrap_or(0),
+                    None,
+                    None,
+                )
             }
             ObsWebSocketVersion::V5 => {
-                let scenes = response["scenes"].as_array()
-                    .ok_or_else(|| "Invalid response format".to_string())?;
-                
-                Ok(scenes.iter()
-                    .filter_map(|scene| scene["sceneName"].as_str())
-                    .map(|s| s.to_string())
-                    .collect())
+                let response = self.send_request(connection_name, "GetStreamStatus", None).await?;
+
+                (
+                    response["outputActive"].as_bool().unwrap_or(false),
+                    response["outputSkippedFrames"].as_u64().unwrap_or(0),
+                    response["outputTotalFrames"].as_u64().unwrap_or(0),
+                    response["outputBytes"].as_u64(),
+                    response["outputCongestion"].as_f64(),
+                )
+            }
+        };
+
+        // Compare against the previous sample so old drops don't mask recovery
+        let previous = {
+            let mut connections = self.connections.lock().unwrap();
+            let connection = connections.get_mut(connection_name)
+                .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+            if update_baseline {
+                connection.last_stream_frames.replace((skipped_frames, total_frames))
+            } else {
+                connection.last_stream_frames
             }
+        };
+
+        let (recent_skipped, recent_total) = recent_frames(previous, skipped_frames, total_frames);
+
+        Ok(StreamHealth {
+            is_streaming,
+            skipped_frames,
+            total_frames,
+            drop_ratio: frame_drop_ratio(skipped_frames, total_frames),
+            recent_drop_ratio: frame_drop_ratio(recent_skipped, recent_total),
+            output_bytes,
+            congestion,
+        })
+    }
+
+    // Sample stream health and emit a warning when drops exceed the threshold
+    pub async fn check_stream_health(&self, connection_name: &str, threshold: f64) -> Result<StreamHealth, String> {
+        let health = self.sample_stream_health(connection_name, true).await?;
+
+        if health.is_streaming && health.recent_drop_ratio >= threshold {
+            let _ = self.event_tx.send(ObsEvent::StreamHealthWarning {
+                connection_name: connection_name.to_string(),
+                drop_ratio: health.recent_drop_ratio,
+                threshold,
+            });
         }
+
+        Ok(health)
     }
 
-    // Helper methods
-    fn generate_request_id(&self, connection: &mut ObsConnection) -> String {
-        connection.request_id_counter += 1;
-        Uuid::new_v4().to_string()
+    // Periodically check stream health until the stream ends, waiting for it
+    // to start first if it hasn't yet
+    pub async fn monitor_stream_health(&self, connection_name: &str, interval: Duration, threshold: f64) -> Result<(), String> {
+        let interval = interval.max(MIN_MONITOR_INTERVAL);
+        let mut has_streamed = false;
+
+        loop {
+            let health = self.check_stream_health(connection_name, threshold).await?;
+            if health.is_streaming {
+                has_streamed = true;
+            } else if has_streamed {
+                break;
+            }
+            tokio::time::sleep(interval).await;
+        }
+
+        // Start the next stream with a fresh baseline
+        let mut connections = self.connections.lock().unwrap();
+        if let Some(connection) = connections.get_mut(connection_name) {
+            connection.last_stream_frames = None;
+        }
+
+        Ok(())
     }
 
-    fn get_protocol_version(&self, connection_name: &str) -> Result<ObsWebSocketVersion, String> {
-        let connections = self.connections.lock().unwrap();
-        let connection = connections.get(connection_name)
-            .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
-        
-        Ok(connection.config.protocol_version)
+    // Monitor stream health in the background, replacing the connection's
+    // current monitor so two never share the sample baseline
+    pub fn start_stream_health_monitor(&self, connection_name: &str, interval: Duration, threshold: f64) -> Result<(), String> {
+        {
+            let mut connections = self.connections.lock().unwrap();
+            if let Some(connection) = connections.get_mut(connection_name) {
+                connection.last_stream_frames = None;
+            }
+        }
+
+        let plugin = self.clone();
+        let name = connection_name.to_string();
+        self.replace_connection_task(connection_name, |c| &mut c.stream_health_monitor, async move {
+            let _ = plugin.monitor_stream_health(&name, interval, threshold).await;
+        })
     }
 
-    // Get connection status
-    pub fn get_connection_status(&self, connection_name: &str) -> Option<ObsConnectionStatus> {
-        let connections = self.connections.lock().unwrap();
-        connections.get(connection_name).map(|c| c.status.clone())
+    // Emit recording progress in the background, replacing the connection's
+    // current listener
+    pub fn start_recording_progress_listener(&self, connection_name: &str, interval: Duration) -> Result<(), String> {
+        let plugin = self.clone();
+        let name = connection_name.to_string();
+        self.replace_connection_task(connection_name, |c| &mut c.recording_progress_listener, async move {
+            let _ = plugin.run_recording_progress_listener(&name, interval).await;
+        })
     }
 
-    // Get all connection names
-    pub fn get_connection_names(&self) -> Vec<String> {
-        let connections = self.connections.lock().unwrap();
-        connections.keys().cloned().collect()
+    // Stop the background recording progress listener. Returns whether one was running.
+    pub fn stop_recording_progress_listener(&self, connection_name: &str) -> Result<bool, String> {
+        self.stop_connection_task(connection_name, |c| &mut c.recording_progress_listener)
     }
 
-    // Remove connection
-    pub fn remove_connection(&self, connection_name: &str) -> Result<(), String> {
+    // Stop the background stream health monitor. Returns whether one was running.
+    pub fn stop_stream_health_monitor(&self, connection_name: &str) -> Result<bool, String> {
+        self.stop_connection_task(connection_name, |c| &mut c.stream_health_monitor)
+    }
+
+    // Spawn a background task into one of a connection's task slots, aborting
+    // the task already there
+    fn replace_connection_task<F>(
+        &self,
+        connection_name: &str,
+        slot: fn(&mut ObsConnection) -> &mut Option<tokio::task::JoinHandle<()>>,
+        task: F,
+    ) -> Result<(), String>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|_| "Background OBS tasks need a running async runtime".to_string())?;
+
         let mut connections = self.connections.lock().unwrap();
-        
-        if connections.remove(connection_name).is_some() {
-            Ok(())
-        } else {
-            Err(format!("Connection '{}' not found", connection_name))
+        let connection = connections.get_mut(connection_name)
+            .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+
+        if let Some(previous) = slot(connection).replace(runtime.spawn(task)) {
+            previous.abort();
         }
+        Ok(())
     }
-}
 
-// Legacy function for backward compatibility
+    fn stop_connection_task(
+        &self,
+        connection_name: &str,
+        slot: fn(&mut ObsConnection) -> &mut Option<tokio::task::JoinHandle<()>>,
+    ) -> Result<bool, String> {
+        let mut connections = self.connections.lock().unwrap();
+        let connection = connections.get_mut(connection_name)
+            .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+
+        Ok(match slot(connection).take() {
+            Some(task) => {
+                let was_running = !task.is_finished();
+                task.abort();
+                was_running
+            }
+            None => false,
+        })
+    }
+
+    // Set streaming service, server and key. A service of "custom" uses
+    // rtmp_custom with the server URL; anything else is a named rtmp_common service
+    pub async fn set_stream_service_settings(
+        &self,
+        connection_name: &str,
+        service: &str,
+        server: &str,
+        key: &str,
+    ) -> Result<(), String> {
+        let (service_type, settings) = build_stream_service_settings(service, server, key)?;
+        self.send_stream_service(connection_name, service_type, &settings).await
+    }
+
+    async fn send_stream_service(&self, connection_name: &str, service_type: &str, settings: &serde_json::Value) -> Result<(), String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                self.send_request(connection_name, "SetStreamSettings", Some(serde_json::json!({
+                    "type": service_type,
+                    "settings": settings,
+                    "save": true
+                }))).await?;
+            }
+            ObsWebSocketVersion::V5 => {
+                self.send_request(connection_name, "SetStreamServiceSettings", Some(serde_json::json!({
+                    "streamServiceType": service_type,
+                    "streamServiceSettings": settings
+                }))).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Get canvas/output resolution and frame rate
+    pub async fn get_video_settings(&self, connection_name: &str) -> Result<VideoSettings, String> {
+        let as_u32 = |value: &serde_json::Value| value.as_u64().map(|v| v as u32);
+
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "GetVideoInfo", None).await?;
+                // v4 reports FPS as a float; keep millihertz precision (e.g. 29.97)
+                let fps = response["fps"].as_f64().ok_or_else(|| "Invalid response format".to_string())?;
+
+                Ok(VideoSettings {
+                    base_width: as_u32(&response["baseWidth"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    base_height: as_u32(&response["baseHeight"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    output_width: as_u32(&response["outputWidth"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    output_height: as_u32(&response["outputHeight"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    fps_numerator: (fps * 1000.0).round() as u32,
+                    fps_denominator: 1000,
+                })
+            }
+            ObsWebSocketVersion::V5 => {
+                let response = self.send_request(connection_name, "GetVideoSettings", None).await?;
+
+                Ok(VideoSettings {
+                    base_width: as_u32(&response["baseWidth"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    base_height: as_u32(&response["baseHeight"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    output_width: as_u32(&response["outputWidth"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    output_height: as_u32(&response["outputHeight"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    fps_numerator: as_u32(&response["fpsNumerator"]).ok_or_else(|| "Invalid response format".to_string())?,
+                    fps_denominator: as_u32(&response["fpsDenominator"]).ok_or_else(|| "Invalid response format".to_string())?,
+                })
+            }
+        }
+    }
+
+    // Set canvas/output resolution and frame rate, returning the applied
+    // settings and any warnings
+    pub async fn set_video_settings(&self, connection_name: &str, settings: VideoSettings) -> Result<(VideoSettings, Vec<String>), String> {
+        let warnings = settings.validate()?;
+
+        if self.get_protocol_version(connection_name)? == ObsWebSocketVersion::V4 {
+            return Err("Changing video settings requires obs-websocket v5".to_string());
+        }
+
+        self.send_request(connection_name, "SetVideoSettings", Some(serde_json::json!({
+            "baseWidth": settings.base_width,
+            "baseHeight": settings.base_height,
+            "outputWidth": settings.output_width,
+            "outputHeight": settings.output_height,
+            "fpsNumerator": settings.fps_numerator,
+            "fpsDenominator": settings.fps_denominator
+        }))).await?;
+
+        let applied = self.get_video_settings(connection_name).await?;
+        Ok((applied, warnings))
+    }
+
+    // Get the input (source) kinds OBS can create
+    pub async fn get_input_kinds(&self, connection_name: &str) -> Result<Vec<String>, String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "GetSourceTypesList", None).await?;
+                let types = response["types"].as_array()
+                    .ok_or_else(|| "Invalid response format".to_string())?;
+
+                Ok(types.iter()
+                    .filter(|t| t["type"] == "input")
+                    .filter_map(|t| t["typeId"].as_str())
+                    .map(|s| s.to_string())
+                    .collect())
+            }
+            ObsWebSocketVersion::V5 => {
+                let response = self.send_request(connection_name, "GetInputKindList", None).await?;
+                let kinds = response["inputKinds"].as_array()
+                    .ok_or_else(|| "Invalid response format".to_string())?;
+
+                Ok(kinds.iter()
+                    .filter_map(|kind| kind.as_str())
+                    .map(|s| s.to_string())
+                    .collect())
+            }
+        }
+    }
+
+    // List the selectable items of an input's list property. When no property
+    // is given, the device property for the input's kind is used.
+    pub async fn get_input_properties(
+        &self,
+        connection_name: &str,
+        input_name: &str,
+        property_name: Option<&str>,
+    ) -> Result<Vec<InputPropertyItem>, String> {
+        if self.get_protocol_version(connection_name)? == ObsWebSocketVersion::V4 {
+            return Err("Listing input properties requires obs-websocket v5".to_string());
+        }
+
+        let (property_name, guessed) = match property_name {
+            Some(property_name) => (property_name.to_string(), false),
+            None => {
+                let response = self.send_request(connection_name, "GetInputSettings", Some(serde_json::json!({
+                    "inputName": input_name
+                }))).await?;
+                let input_kind = response["inputKind"]
+                    .as_str()
+                    .ok_or_else(|| format!("Input '{}' not found", input_name))?;
+                (device_property_for_input_kind(input_kind).to_string(), true)
+            }
+        };
+
+        let response = self.send_request(connection_name, "GetInputPropertiesListPropertyItems", Some(serde_json::json!({
+            "inputName": input_name,
+            "propertyName": property_name
+        }))).await;
+
+        // Kinds we don't know the device property of may have no such property
+        // at all, which means there are no devices to list
+        let response = match response {
+            Err(error) if guessed && error.to_lowercase().contains("property") => return Ok(Vec::new()),
+            response => response?,
+        };
+
+        // Inputs without enumerable devices simply have no items
+        let items = match response["propertyItems"].as_array() {
+            Some(items) => items,
+            None => return Ok(Vec::new()),
+        };
+
+        Ok(items.iter()
+            .map(|item| InputPropertyItem {
+                name: item["itemName"].as_str().unwrap_or("").to_string(),
+                value: item["itemValue"].clone(),
+                enabled: item["itemEnabled"].as_bool().unwrap_or(true),
+            })
+            .collect())
+    }
+
+    // Set the text of a text source, returning the text that was applied
+    pub async fn set_text_source(&self, connection_name: &str, source_name: &str, text: &str) -> Result<String, String> {
+        let text = sanitize_text_source(text);
+
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "GetSourceSettings", Some(serde_json::json!({
+                    "sourceName": source_name
+                }))).await?;
+                validate_text_source_kind(source_name, response["sourceType"].as_str())?;
+
+                self.send_request(connection_name, "SetSourceSettings", Some(serde_json::json!({
+                    "sourceName": source_name,
+                    "sourceSettings": { "text": text }
+                }))).await?;
+            }
+            ObsWebSocketVersion::V5 => {
+                let response = self.send_request(connection_name, "GetInputSettings", Some(serde_json::json!({
+                    "inputName": source_name
+                }))).await?;
+                validate_text_source_kind(source_name, response["inputKind"].as_str())?;
+
+                self.send_request(connection_name, "SetInputSettings", Some(serde_json::json!({
+                    "inputName": source_name,
+                    "inputSettings": { "text": text },
+                    "overlay": true
+                }))).await?;
+            }
+        }
+
+        Ok(text)
+    }
+
+    // Update the configured recording indicator; failures don't affect recording
+    async fn update_recording_indicator(&self, connection_name: &str, is_recording: bool) {
+        let source_name = {
+            let connections = self.connections.lock().unwrap();
+            connections.get(connection_name)
+                .and_then(|c| c.config.recording_indicator_source.clone())
+        };
+
+        let Some(source_name) = source_name else {
+            return;
+        };
+
+        let settings_request = match self.get_protocol_version(connection_name) {
+            Ok(ObsWebSocketVersion::V4) => "SetSourceSettings",
+            Ok(ObsWebSocketVersion::V5) => "SetInputSettings",
+            Err(_) => return,
+        };
+        if !self.supports_request(connection_name, settings_request) {
+            return;
+        }
+
+        let text = if is_recording { RECORDING_INDICATOR_TEXT } else { "" };
+        if let Err(e) = self.set_text_source(connection_name, &source_name, text).await {
+            let _ = self.event_tx.send(ObsEvent::Error {
+                connection_name: connection_name.to_string(),
+                error: format!("Failed to update recording indicator '{}': {}", source_name, e),
+            });
+        }
+    }
+
+    // Load saved quality profiles and keep saving to the same file
+    pub fn load_quality_profiles(&self, path: &Path) -> Result<usize, String> {
+        let loaded: Vec<QualityProfile> = match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| format!("Invalid quality profiles file '{}': {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read quality profiles '{}': {}", path.display(), e)),
+        };
+
+        // All or nothing, so a bad entry doesn't leave a partial set
+        for profile in &loaded {
+            profile.validate()
+                .map_err(|e| format!("Invalid quality profile '{}' in '{}': {}", profile.name, path.display(), e))?;
+        }
+
+        let count = loaded.len();
+        {
+            let mut profiles = self.quality_profiles.lock().unwrap();
+            for profile in loaded {
+                profiles.insert(profile.name.clone(), profile);
+            }
+        }
+
+        *self.quality_profiles_path.lock().unwrap() = Some(path.to_path_buf());
+        Ok(count)
+    }
+
+    // Save (or replace) a named quality profile
+    pub fn save_quality_profile(&self, profile: QualityProfile) -> Result<(), String> {
+        profile.validate()?;
+
+        self.quality_profiles.lock().unwrap().insert(profile.name.clone(), profile);
+        self.persist_quality_profiles()
+    }
+
+    fn persist_quality_profiles(&self) -> Result<(), String> {
+        let Some(path) = self.quality_profiles_path.lock().unwrap().clone() else {
+            return Ok(());
+        };
+
+        let contents = serde_json::to_string_pretty(&self.get_quality_profiles())
+            .map_err(|e| format!("Failed to serialize quality profiles: {}", e))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("Failed to save quality profiles to '{}': {}", path.display(), e))
+    }
+
+    // Get all quality profiles, sorted by name
+    pub fn get_quality_profiles(&self) -> Vec<QualityProfile> {
+        let profiles = self.quality_profiles.lock().unwrap();
+        let mut profiles: Vec<QualityProfile> = profiles.values().cloned().collect();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        profiles
+    }
+
+    // Push a quality profile's encoder settings to OBS. Only simple output
+    // mode keeps these as profile parameters; advanced mode stores them in
+    // encoder files obs-websocket can't reach.
+    pub async fn apply_quality_profile(&self, connection_name: &str, profile_name: &str) -> Result<QualityProfile, String> {
+        let profile = {
+            let profiles = self.quality_profiles.lock().unwrap();
+            profiles.get(profile_name)
+                .cloned()
+                .ok_or_else(|| format!("Quality profile '{}' not found", profile_name))?
+        };
+        profile.validate()?;
+
+        if self.get_protocol_version(connection_name)? == ObsWebSocketVersion::V4 {
+            return Err("Quality profiles require obs-websocket v5 (SetProfileParameter)".to_string());
+        }
+
+        let output_mode = self.get_profile_parameter(connection_name, "Output", "Mode").await?
+            .unwrap_or_else(|| "Simple".to_string());
+        if output_mode != "Simple" {
+            return Err(format!(
+                "OBS is in {} output mode; quality profiles can only be applied in Simple output mode",
+                output_mode
+            ));
+        }
+
+        let preset_key = simple_output_preset_key(&profile.encoder).ok_or_else(|| {
+            format!("Encoder '{}' has no preset setting in Simple output mode", profile.encoder)
+        })?;
+
+        let mut parameters = vec![
+            ("VBitrate", profile.video_bitrate_kbps.to_string()),
+            ("StreamEncoder", profile.encoder.clone()),
+            ("RecEncoder", profile.encoder.clone()),
+            (preset_key, profile.preset.clone()),
+        ];
+
+        // Check the keyframe interval can be applied before changing anything
+        if profile.encoder == SIMPLE_OUTPUT_X264_ENCODER {
+            let video_settings = self.get_video_settings(connection_name).await?;
+            let fps = video_settings.fps_numerator as f64 / video_settings.fps_denominator.max(1) as f64;
+            let keyint_frames = (fps * profile.keyframe_interval_sec as f64).round() as u32;
+            let x264_settings = self.get_profile_parameter(connection_name, "SimpleOutput", "x264Settings").await?
+                .unwrap_or_default();
+            parameters.push(("x264Settings", with_x264_option(&x264_settings, "keyint", &keyint_frames.to_string())));
+        } else if profile.keyframe_interval_sec != SIMPLE_OUTPUT_KEYFRAME_INTERVAL_SEC {
+            return Err(format!(
+                "Simple output mode uses a fixed {}s keyframe interval for encoder '{}'; only x264 can change it",
+                SIMPLE_OUTPUT_KEYFRAME_INTERVAL_SEC, profile.encoder
+            ));
+        }
+
+        for (parameter_name, parameter_value) in parameters {
+            self.send_request(connection_name, "SetProfileParameter", Some(serde_json::json!({
+                "parameterCategory": "SimpleOutput",
+                "parameterName": parameter_name,
+                "parameterValue": parameter_value
+            }))).await?;
+        }
+
+        Ok(profile)
+    }
+
+    async fn get_profile_parameter(&self, connection_name: &str, category: &str, name: &str) -> Result<Option<String>, String> {
+        let response = self.send_request(connection_name, "GetProfileParameter", Some(serde_json::json!({
+            "parameterCategory": category,
+            "parameterName": name
+        }))).await?;
+
+        Ok(response["parameterValue"].as_str()
+            .or_else(|| response["defaultParameterValue"].as_str())
+            .map(|s| s.to_string()))
+    }
+
+    // Start virtual camera
+    pub async fn start_virtual_camera(&self, connection_name: &str) -> Result<bool, String> {
+        self.send_request(connection_name, "StartVirtualCam", None).await
+            .map_err(virtual_camera_error)?;
+
+        self.get_virtual_camera_status(connection_name).await
+    }
+
+    // Stop virtual camera
+    pub async fn stop_virtual_camera(&self, connection_name: &str) -> Result<bool, String> {
+        self.send_request(connection_name, "StopVirtualCam", None).await
+            .map_err(virtual_camera_error)?;
+
+        self.get_virtual_camera_status(connection_name).await
+    }
+
+    // Get virtual camera status
+    pub async fn get_virtual_camera_status(&self, connection_name: &str) -> Result<bool, String> {
+        let response = self.send_request(connection_name, "GetVirtualCamStatus", None).await
+            .map_err(virtual_camera_error)?;
+
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                Ok(response["isVirtualCam"].as_bool().unwrap_or(false))
+            }
+            ObsWebSocketVersion::V5 => {
+                Ok(response["outputActive"].as_bool().unwrap_or(false))
+            }
+        }
+    }
+
+    // Enable or disable studio mode, returning the resulting state
+    pub async fn set_studio_mode(&self, connection_name: &str, enabled: bool) -> Result<bool, String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let request_type = if enabled { "EnableStudioMode" } else { "DisableStudioMode" };
+                self.send_request(connection_name, request_type, None).await?;
+            }
+            ObsWebSocketVersion::V5 => {
+                self.send_request(connection_name, "SetStudioModeEnabled", Some(serde_json::json!({
+                    "studioModeEnabled": enabled
+                }))).await?;
+            }
+        }
+
+        self.get_studio_mode_enabled(connection_name).await
+    }
+
+    // Get studio mode state
+    pub async fn get_studio_mode_enabled(&self, connection_name: &str) -> Result<bool, String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "GetStudioModeStatus", None).await?;
+                Ok(response["studio-mode"].as_bool().unwrap_or(false))
+            }
+            ObsWebSocketVersion::V5 => {
+                let response = self.send_request(connection_name, "GetStudioModeEnabled", None).await?;
+                Ok(response["studioModeEnabled"].as_bool().unwrap_or(false))
+            }
+        }
+    }
+
+    // Stage a scene in preview (studio mode only)
+    pub async fn set_preview_scene(&self, connection_name: &str, scene_name: &str) -> Result<(), String> {
+        if !self.get_studio_mode_enabled(connection_name).await? {
+            return Err("Studio mode must be enabled to set a preview scene".to_string());
+        }
+
+        let scenes = self.get_scenes(connection_name).await?;
+        if !scenes.iter().any(|s| s == scene_name) {
+            return Err(format!("Scene '{}' not found", scene_name));
+        }
+
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                self.send_request(connection_name, "SetPreviewScene", Some(serde_json::json!({
+                    "scene-name": scene_name
+                }))).await?;
+            }
+            ObsWebSocketVersion::V5 => {
+                self.send_request(connection_name, "SetCurrentPreviewScene", Some(serde_json::json!({
+                    "sceneName": scene_name
+                }))).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Take the preview scene to program (studio mode only)
+    pub async fn trigger_transition(&self, connection_name: &str) -> Result<(), String> {
+        if !self.get_studio_mode_enabled(connection_name).await? {
+            return Err("Studio mode must be enabled to trigger a transition".to_string());
+        }
+
+        let request_type = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => "TransitionToProgram",
+            ObsWebSocketVersion::V5 => "TriggerStudioModeTransition",
+        };
+
+        self.send_request(connection_name, request_type, None).await?;
+        Ok(())
+    }
+
+    // Get all scenes
+    pub async fn get_scenes(&self, connection_name: &str) -> Result<Vec<String>, String> {
+        let response = self.send_request(connection_name, "GetSceneList", None).await?;
+        
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let scenes = response["scenes"].as_array()
+                    .ok_or_else(|| "Invalid response format".to_string())?;
+                
+                Ok(scenes.iter()
+                    .filter_map(|scene| scene["scene-name"].as_str())
+                    .map(|s| s.to_string())
+                    .collect())
+            }
+            ObsWebSocketVersion::V5 => {
+                let scenes = response["scenes"].as_array()
+                    .ok_or_else(|| "Invalid response format".to_string())?;
+                
+                Ok(scenes.iter()
+                    .filter_map(|scene| scene["sceneName"].as_str())
+                    .map(|s| s.to_string())
+                    .collect())
+            }
+        }
+    }
+
+    // Measure round-trip time to OBS with a lightweight request
+    pub async fn ping(&self, connection_name: &str) -> Result<u64, String> {
+        self.ping_with_timeout(connection_name, PING_TIMEOUT).await
+    }
+
+    async fn ping_with_timeout(&self, connection_name: &str, timeout: Duration) -> Result<u64, String> {
+        let started = Instant::now();
+
+        self.send_request_with_timeout(connection_name, "GetVersion", None, timeout).await?;
+
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        let mut connections = self.connections.lock().unwrap();
+        if let Some(connection) = connections.get_mut(connection_name) {
+            connection.last_ping_ms = Some(latency_ms);
+        }
+
+        Ok(latency_ms)
+    }
+
+    // Ping every authenticated connection once, marking unresponsive ones
+    // dead, and retry dead connections whose backoff has elapsed. Returns the
+    // names of connections that are still dead.
+    pub async fn heartbeat_once(&self, config: &ObsHeartbeatConfig) -> Vec<String> {
+        let connection_names: Vec<String> = {
+            let connections = self.connections.lock().unwrap();
+            connections.values()
+                .filter(|c| c.status == ObsConnectionStatus::Authenticated)
+                .map(|c| c.config.name.clone())
+                .collect()
+        };
+
+        let timeout = Duration::from_millis(config.timeout_ms);
+
+        for connection_name in connection_names {
+            let error = match self.ping_with_timeout(&connection_name, timeout).await {
+                Ok(_) => continue,
+                Err(e) => e,
+            };
+
+            let status = ObsConnectionStatus::Error(format!("Heartbeat failed: {}", error));
+            {
+                let mut connections = self.connections.lock().unwrap();
+                if let Some(connection) = connections.get_mut(&connection_name) {
+                    // Skip if the connection was explicitly disconnected meanwhile
+                    if connection.status != ObsConnectionStatus::Authenticated {
+                        continue;
+                    }
+                    if let Some(reader_task) = connection.reader_task.take() {
+                        reader_task.abort();
+                    }
+                    connection.websocket = None;
+                    connection.pending_requests.clear();
+                    connection.status = status.clone();
+                    connection.next_reconnect_at = None;
+                }
+            }
+
+            let _ = self.event_tx.send(ObsEvent::ConnectionStatusChanged {
+                connection_name: connection_name.clone(),
+                status,
+            });
+        }
+
+        // Enabled connections in error (failed ping, lost socket or failed
+        // connect) whose backoff has elapsed
+        let now = Instant::now();
+        let reconnect_names: Vec<String> = {
+            let connections = self.connections.lock().unwrap();
+            connections.values()
+                .filter(|c| c.config.enabled && matches!(c.status, ObsConnectionStatus::Error(_)))
+                .filter(|c| c.next_reconnect_at.map(|at| at <= now).unwrap_or(true))
+                .map(|c| c.config.name.clone())
+                .collect()
+        };
+
+        for connection_name in &reconnect_names {
+            if let Err(e) = self.connect_obs(connection_name).await {
+                let mut connections = self.connections.lock().unwrap();
+                if let Some(connection) = connections.get_mut(connection_name) {
+                    connection.reconnect_attempts += 1;
+                    connection.next_reconnect_at = Some(Instant::now() + config.reconnect_backoff(connection.reconnect_attempts));
+                }
+                drop(connections);
+
+                let _ = self.event_tx.send(ObsEvent::Error {
+                    connection_name: connection_name.clone(),
+                    error: format!("Reconnect after heartbeat failure failed: {}", e),
+                });
+            }
+        }
+
+        let connections = self.connections.lock().unwrap();
+        let mut dead: Vec<String> = connections.values()
+            .filter(|c| c.config.enabled && matches!(c.status, ObsConnectionStatus::Error(_)))
+            .map(|c| c.config.name.clone())
+            .collect();
+        dead.sort();
+        dead
+    }
+
+    // Run heartbeats forever, picking up config changes each round
+    pub async fn run_heartbeat(&self) {
+        loop {
+            let config = self.get_heartbeat_config();
+            tokio::time::sleep(Duration::from_millis(config.interval_ms)).await;
+            self.heartbeat_once(&config).await;
+        }
+    }
+
+    pub fn set_heartbeat_config(&self, config: ObsHeartbeatConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.heartbeat_config.lock().unwrap() = config;
+        Ok(())
+    }
+
+    pub fn get_heartbeat_config(&self) -> ObsHeartbeatConfig {
+        *self.heartbeat_config.lock().unwrap()
+    }
+
+    // Get scene item transform
+    pub async fn get_scene_item_transform(
+        &self,
+        connection_name: &str,
+        scene_name: &str,
+        item_name: &str,
+    ) -> Result<SceneItemTransform, String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "GetSceneItemProperties", Some(serde_json::json!({
+                    "scene-name": scene_name,
+                    "item": item_name
+                }))).await?;
+
+                if response["position"].is_null() {
+                    return Err(format!("Scene item '{}' not found in scene '{}'", item_name, scene_name));
+                }
+
+                Ok(SceneItemTransform {
+                    x: response["position"]["x"].as_f64().unwrap_or(0.0),
+                    y: response["position"]["y"].as_f64().unwrap_or(0.0),
+                    scale_x: response["scale"]["x"].as_f64().unwrap_or(1.0),
+                    scale_y: response["scale"]["y"].as_f64().unwrap_or(1.0),
+                    rotation: response["rotation"].as_f64().unwrap_or(0.0),
+                })
+            }
+            ObsWebSocketVersion::V5 => {
+                let scene_item_id = self.get_scene_item_id(connection_name, scene_name, item_name).await?;
+                let response = self.send_request(connection_name, "GetSceneItemTransform", Some(serde_json::json!({
+                    "sceneName": scene_name,
+                    "sceneItemId": scene_item_id
+                }))).await?;
+
+                let transform = &response["sceneItemTransform"];
+                Ok(SceneItemTransform {
+                    x: transform["positionX"].as_f64().unwrap_or(0.0),
+                    y: transform["positionY"].as_f64().unwrap_or(0.0),
+                    scale_x: transform["scaleX"].as_f64().unwrap_or(1.0),
+                    scale_y: transform["scaleY"].as_f64().unwrap_or(1.0),
+                    rotation: transform["rotation"].as_f64().unwrap_or(0.0),
+                })
+            }
+        }
+    }
+
+    // Set scene item transform, returning the clamped values that were applied
+    pub async fn set_scene_item_transform(
+        &self,
+        connection_name: &str,
+        scene_name: &str,
+        item_name: &str,
+        transform: SceneItemTransform,
+    ) -> Result<SceneItemTransform, String> {
+        let applied = transform.clamped();
+
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                // Validate the item exists before changing it
+                self.get_scene_item_transform(connection_name, scene_name, item_name).await?;
+
+                self.send_request(connection_name, "SetSceneItemProperties", Some(serde_json::json!({
+                    "scene-name": scene_name,
+                    "item": item_name,
+                    "position": { "x": applied.x, "y": applied.y },
+                    "scale": { "x": applied.scale_x, "y": applied.scale_y },
+                    "rotation": applied.rotation
+                }))).await?;
+            }
+            ObsWebSocketVersion::V5 => {
+                let scene_item_id = self.get_scene_item_id(connection_name, scene_name, item_name).await?;
+
+                self.send_request(connection_name, "SetSceneItemTransform", Some(serde_json::json!({
+                    "sceneName": scene_name,
+                    "sceneItemId": scene_item_id,
+                    "sceneItemTransform": {
+                        "positionX": applied.x,
+                        "positionY": applied.y,
+                        "scaleX": applied.scale_x,
+                        "scaleY": applied.scale_y,
+                        "rotation": applied.rotation
+                    }
+                }))).await?;
+            }
+        }
+
+        Ok(applied)
+    }
+
+    // Resolve a scene item's numeric id (v5 only)
+    async fn get_scene_item_id(&self, connection_name: &str, scene_name: &str, item_name: &str) -> Result<i64, String> {
+        let response = self.send_request(connection_name, "GetSceneItemId", Some(serde_json::json!({
+            "sceneName": scene_name,
+            "sourceName": item_name
+        }))).await?;
+
+        response["sceneItemId"]
+            .as_i64()
+            .ok_or_else(|| format!("Scene item '{}' not found in scene '{}'", item_name, scene_name))
+    }
+
+    // Query OBS version and supported requests and store them on the connection
+    pub async fn detect_capabilities(&self, connection_name: &str) -> Result<ObsCapabilities, String> {
+        // Clear stale capabilities so GetVersion itself isn't gated
+        {
+            let mut connections = self.connections.lock().unwrap();
+            if let Some(connection) = connections.get_mut(connection_name) {
+                connection.capabilities = None;
+            }
+        }
+
+        let response = self.send_request(connection_name, "GetVersion", None).await?;
+
+        let capabilities = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => ObsCapabilities {
+                obs_version: response["obs-studio-version"].as_str().map(|s| s.to_string()),
+                websocket_version: response["obs-websocket-version"].as_str().map(|s| s.to_string()),
+                available_requests: response["available-requests"]
+                    .as_str()
+                    .unwrap_or("")
+                    .split(',')
+                    .filter(|r| !r.is_empty())
+                    .map(|r| r.to_string())
+                    .collect(),
+            },
+            ObsWebSocketVersion::V5 => ObsCapabilities {
+                obs_version: response["obsVersion"].as_str().map(|s| s.to_string()),
+                websocket_version: response["obsWebSocketVersion"].as_str().map(|s| s.to_string()),
+                available_requests: response["availableRequests"]
+                    .as_array()
+                    .map(|requests| requests.iter()
+                        .filter_map(|r| r.as_str())
+                        .map(|r| r.to_string())
+                        .collect())
+                    .unwrap_or_default(),
+            },
+        };
+
+        // An empty list means OBS didn't report it; don't gate anything then
+        if capabilities.available_requests.is_empty() {
+            return Ok(capabilities);
+        }
+
+        let mut connections = self.connections.lock().unwrap();
+        if let Some(connection) = connections.get_mut(connection_name) {
+            connection.capabilities = Some(capabilities.clone());
+        }
+
+        Ok(capabilities)
+    }
+
+    // Get detected capabilities
+    pub fn get_capabilities(&self, connection_name: &str) -> Option<ObsCapabilities> {
+        let connections = self.connections.lock().unwrap();
+        connections.get(connection_name).and_then(|c| c.capabilities.clone())
+    }
+
+    // Whether a request is supported; assumed true until capabilities are known
+    pub fn supports_request(&self, connection_name: &str, request_type: &str) -> bool {
+        self.get_capabilities(connection_name)
+            .map(|capabilities| capabilities.supports(request_type))
+            .unwrap_or(true)
+    }
+
+    // Capture the parts of an OBS configuration we can read back. Scene items
+    // and filters aren't captured, so imported scenes are created empty.
+    pub async fn export_config(&self, connection_name: &str) -> Result<ObsConfigSnapshot, String> {
+        let mut not_exported = Vec::new();
+
+        let inputs = match self.export_inputs(connection_name, &mut not_exported).await {
+            Ok(inputs) => inputs,
+            Err(e) => {
+                not_exported.push(format!("inputs: {}", e));
+                Vec::new()
+            }
+        };
+
+        let stream_service = match self.get_stream_service(connection_name).await {
+            Ok(stream_service) => Some(stream_service),
+            Err(e) => {
+                not_exported.push(format!("stream_service: {}", e));
+                None
+            }
+        };
+
+        Ok(ObsConfigSnapshot {
+            obs_version: self.get_capabilities(connection_name).and_then(|c| c.obs_version),
+            scene_collection: self.get_current_scene_collection(connection_name).await.ok(),
+            profile: self.get_current_profile(connection_name).await.ok(),
+            scenes: self.get_scenes(connection_name).await?,
+            current_scene: self.get_current_scene(connection_name).await.ok(),
+            recording_directory: self.get_recording_directory(connection_name).await.ok(),
+            studio_mode: self.get_studio_mode_enabled(connection_name).await.ok(),
+            video_settings: self.get_video_settings(connection_name).await.ok(),
+            inputs,
+            stream_service,
+            not_exported,
+        })
+    }
+
+    // Every input with its settings; inputs that can't be read are noted and skipped
+    async fn export_inputs(&self, connection_name: &str, not_exported: &mut Vec<String>) -> Result<Vec<ObsInputSnapshot>, String> {
+        let mut inputs = Vec::new();
+        for (name, kind) in self.get_input_list(connection_name).await? {
+            match self.send_request(connection_name, "GetInputSettings", Some(serde_json::json!({
+                "inputName": name
+            }))).await {
+                Ok(response) => inputs.push(ObsInputSnapshot {
+                    name,
+                    kind,
+                    settings: response["inputSettings"].clone(),
+                }),
+                Err(e) => not_exported.push(format!("input '{}': {}", name, e)),
+            }
+        }
+
+        Ok(inputs)
+    }
+
+    // Names and kinds of all inputs (v5 only)
+    async fn get_input_list(&self, connection_name: &str) -> Result<Vec<(String, String)>, String> {
+        if self.get_protocol_version(connection_name)? == ObsWebSocketVersion::V4 {
+            return Err("Exporting inputs requires obs-websocket v5".to_string());
+        }
+
+        let response = self.send_request(connection_name, "GetInputList", None).await?;
+        let inputs = response["inputs"]
+            .as_array()
+            .ok_or_else(|| "Invalid response format".to_string())?;
+
+        Ok(inputs.iter()
+            .filter_map(|input| {
+                let name = input["inputName"].as_str()?;
+                let kind = input["inputKind"].as_str().unwrap_or("");
+                Some((name.to_string(), kind.to_string()))
+            })
+            .collect())
+    }
+
+    // Current stream service with the stream key removed
+    async fn get_stream_service(&self, connection_name: &str) -> Result<ObsStreamServiceSnapshot, String> {
+        let (service_type, mut settings) = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "GetStreamSettings", None).await?;
+                (response["type"].clone(), response["settings"].clone())
+            }
+            ObsWebSocketVersion::V5 => {
+                let response = self.send_request(connection_name, "GetStreamServiceSettings", None).await?;
+                (response["streamServiceType"].clone(), response["streamServiceSettings"].clone())
+            }
+        };
+
+        let service_type = service_type
+            .as_str()
+            .ok_or_else(|| "Invalid response format".to_string())?
+            .to_string();
+        if let Some(settings) = settings.as_object_mut() {
+            settings.remove("key");
+        }
+
+        Ok(ObsStreamServiceSnapshot { service_type, settings })
+    }
+
+    // Apply a snapshot, continuing past parts this instance can't take
+    pub async fn import_config(&self, connection_name: &str, snapshot: &ObsConfigSnapshot) -> Result<ObsConfigImportReport, String> {
+        let mut report = ObsConfigImportReport::default();
+
+        // Scene collection first since it replaces the scene list
+        if let Some(collection_name) = &snapshot.scene_collection {
+            match self.set_scene_collection(connection_name, collection_name).await {
+                Ok(_) => report.applied.push(format!("scene_collection: {}", collection_name)),
+                Err(e) => report.skipped.push(format!("scene_collection: {}", e)),
+            }
+        }
+
+        if let Some(profile_name) = &snapshot.profile {
+            match self.set_current_profile(connection_name, profile_name).await {
+                Ok(_) => report.applied.push(format!("profile: {}", profile_name)),
+                Err(e) => report.skipped.push(format!("profile: {}", e)),
+            }
+        }
+
+        // Create scenes that are missing (v5 only; v4 has no CreateScene)
+        let existing_scenes = self.get_scenes(connection_name).await?;
+        for scene_name in snapshot.scenes.iter().filter(|s| !existing_scenes.contains(s)) {
+            if self.get_protocol_version(connection_name)? == ObsWebSocketVersion::V4 {
+                report.skipped.push(format!("scene '{}': creating scenes requires obs-websocket v5", scene_name));
+                continue;
+            }
+
+            match self.send_request(connection_name, "CreateScene", Some(serde_json::json!({
+                "sceneName": scene_name
+            }))).await {
+                Ok(_) => report.applied.push(format!("scene: {} (created empty)", scene_name)),
+                Err(e) => report.skipped.push(format!("scene '{}': {}", scene_name, e)),
+            }
+        }
+
+        // Inputs can only be updated; creating them needs the scene items we don't export
+        if !snapshot.inputs.is_empty() {
+            match self.get_input_list(connection_name).await {
+                Ok(existing_inputs) => {
+                    for input in &snapshot.inputs {
+                        if !existing_inputs.iter().any(|(name, _)| name == &input.name) {
+                            report.skipped.push(format!("input '{}': not present in this OBS", input.name));
+                            continue;
+                        }
+
+                        match self.send_request(connection_name, "SetInputSettings", Some(serde_json::json!({
+                            "inputName": input.name,
+                            "inputSettings": input.settings,
+                            "overlay": true
+                        }))).await {
+                            Ok(_) => report.applied.push(format!("input: {}", input.name)),
+                            Err(e) => report.skipped.push(format!("input '{}': {}", input.name, e)),
+                        }
+                    }
+                }
+                Err(e) => report.skipped.push(format!("inputs: {}", e)),
+            }
+        }
+
+        // The key isn't exported, so the target keeps its own stream key
+        if let Some(stream_service) = &snapshot.stream_service {
+            match self.send_stream_service(connection_name, &stream_service.service_type, &stream_service.settings).await {
+                Ok(_) => report.applied.push(format!("stream_service: {} (stream key not changed)", stream_service.service_type)),
+                Err(e) => report.skipped.push(format!("stream_service: {}", e)),
+            }
+        }
+
+        if let Some(directory) = &snapshot.recording_directory {
+            match self.send_recording_directory(connection_name, directory).await {
+                Ok(_) => report.applied.push(format!("recording_directory: {}", directory)),
+                Err(e) => report.skipped.push(format!("recording_directory: {}", e)),
+            }
+        }
+
+        if let Some(settings) = snapshot.video_settings {
+            match self.set_video_settings(connection_name, settings).await {
+                Ok(_) => report.applied.push("video_settings".to_string()),
+                Err(e) => report.skipped.push(format!("video_settings: {}", e)),
+            }
+        }
+
+        if let Some(enabled) = snapshot.studio_mode {
+            match self.set_studio_mode(connection_name, enabled).await {
+                Ok(_) => report.applied.push(format!("studio_mode: {}", enabled)),
+                Err(e) => report.skipped.push(format!("studio_mode: {}", e)),
+            }
+        }
+
+        if let Some(scene_name) = &snapshot.current_scene {
+            match self.set_current_scene(connection_name, scene_name).await {
+                Ok(_) => report.applied.push(format!("current_scene: {}", scene_name)),
+                Err(e) => report.skipped.push(format!("current_scene: {}", e)),
+            }
+        }
+
+        report.skipped.extend(snapshot.not_exported.iter().cloned());
+
+        Ok(report)
+    }
+
+    // Start recording on all authenticated connections
+    pub async fn start_all_recording(&self) -> Vec<BulkOperationResult> {
+        self.run_on_all_connections("start_recording", |name| async move {
+            self.start_recording(&name).await
+        }).await
+    }
+
+    // Stop recording on all authenticated connections
+    pub async fn stop_all_recording(&self) -> Vec<BulkOperationResult> {
+        self.run_on_all_connections("stop_recording", |name| async move {
+            self.stop_recording(&name).await.map(|_| ())
+        }).await
+    }
+
+    // Switch all authenticated connections to the same scene
+    pub async fn change_all_scenes(&self, scene_name: &str) -> Vec<BulkOperationResult> {
+        self.run_on_all_connections("change_scene", |name| async move {
+            self.set_current_scene(&name, scene_name).await
+        }).await
+    }
+
+    // Run an operation concurrently on every authenticated connection,
+    // continuing past individual failures
+    async fn run_on_all_connections<F, Fut>(&self, operation: &str, op: F) -> Vec<BulkOperationResult>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let connection_names: Vec<String> = {
+            let connections = self.connections.lock().unwrap();
+            connections.values()
+                .filter(|c| c.status == ObsConnectionStatus::Authenticated)
+                .map(|c| c.config.name.clone())
+                .collect()
+        };
+
+        let tasks = connection_names.into_iter().map(|name| {
+            let fut = op(name.clone());
+            async move {
+                let result = fut.await;
+
+                let _ = self.event_tx.send(ObsEvent::BulkOperationProgress {
+                    operation: operation.to_string(),
+                    connection_name: name.clone(),
+                    success: result.is_ok(),
+                    error: result.clone().err(),
+                });
+
+                BulkOperationResult {
+                    connection_name: name,
+                    success: result.is_ok(),
+                    error: result.err(),
+                }
+            }
+        });
+
+        futures_util::future::join_all(tasks).await
+    }
+
+    // Get all scene collections
+    pub async fn get_scene_collections(&self, connection_name: &str) -> Result<Vec<String>, String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "ListSceneCollections", None).await?;
+                let collections = response["scene-collections"].as_array()
+                    .ok_or_else(|| "Invalid response format".to_string())?;
+
+                Ok(collections.iter()
+                    .filter_map(|collection| collection["sc-name"].as_str())
+                    .map(|s| s.to_string())
+                    .collect())
+            }
+            ObsWebSocketVersion::V5 => {
+                let response = self.send_request(connection_name, "GetSceneCollectionList", None).await?;
+                let collections = response["sceneCollections"].as_array()
+                    .ok_or_else(|| "Invalid response format".to_string())?;
+
+                Ok(collections.iter()
+                    .filter_map(|collection| collection.as_str())
+                    .map(|s| s.to_string())
+                    .collect())
+            }
+        }
+    }
+
+    // Get current scene collection
+    pub async fn get_current_scene_collection(&self, connection_name: &str) -> Result<String, String> {
+        let (request_type, field) = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => ("GetCurrentSceneCollection", "sc-name"),
+            ObsWebSocketVersion::V5 => ("GetSceneCollectionList", "currentSceneCollectionName"),
+        };
+
+        let response = self.send_request(connection_name, request_type, None).await?;
+        response[field]
+            .as_str()
+            .ok_or_else(|| "Invalid response format".to_string())
+            .map(|s| s.to_string())
+    }
+
+    // Switch scene collection and wait until OBS has loaded it,
+    // returning the scenes of the new collection
+    pub async fn set_scene_collection(&self, connection_name: &str, collection_name: &str) -> Result<Vec<String>, String> {
+        self.set_scene_collection_with_timeout(connection_name, collection_name, SCENE_COLLECTION_LOAD_TIMEOUT).await
+    }
+
+    async fn set_scene_collection_with_timeout(
+        &self,
+        connection_name: &str,
+        collection_name: &str,
+        load_timeout: Duration,
+    ) -> Result<Vec<String>, String> {
+        let collections = self.get_scene_collections(connection_name).await?;
+        if !collections.iter().any(|c| c == collection_name) {
+            return Err(format!("Scene collection '{}' not found", collection_name));
+        }
+
+        let request_data = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => serde_json::json!({ "sc-name": collection_name }),
+            ObsWebSocketVersion::V5 => serde_json::json!({ "sceneCollectionName": collection_name }),
+        };
+        self.send_request(connection_name, "SetCurrentSceneCollection", Some(request_data)).await?;
+
+        // OBS answers before the collection has finished loading; poll until
+        // it reports the new collection and its scene list is available
+        let scenes = tokio::time::timeout(load_timeout, async {
+            loop {
+                if self.get_current_scene_collection(connection_name).await.ok().as_deref() == Some(collection_name) {
+                    if let Ok(scenes) = self.get_scenes(connection_name).await {
+                        return scenes;
+                    }
+                }
+                tokio::time::sleep(SCENE_COLLECTION_POLL_INTERVAL).await;
+            }
+        })
+        .await
+        .map_err(|_| format!("Timed out waiting for scene collection '{}' to load", collection_name))?;
+
+        let _ = self.event_tx.send(ObsEvent::SceneCollectionChanged {
+            connection_name: connection_name.to_string(),
+            collection_name: collection_name.to_string(),
+        });
+
+        Ok(scenes)
+    }
+
+    // Get all profiles
+    pub async fn get_profiles(&self, connection_name: &str) -> Result<Vec<String>, String> {
+        match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => {
+                let response = self.send_request(connection_name, "ListProfiles", None).await?;
+                let profiles = response["profiles"].as_array()
+                    .ok_or_else(|| "Invalid response format".to_string())?;
+
+                Ok(profiles.iter()
+                    .filter_map(|profile| profile["profile-name"].as_str())
+                    .map(|s| s.to_string())
+                    .collect())
+            }
+            ObsWebSocketVersion::V5 => {
+                let response = self.send_request(connection_name, "GetProfileList", None).await?;
+                let profiles = response["profiles"].as_array()
+                    .ok_or_else(|| "Invalid response format".to_string())?;
+
+                Ok(profiles.iter()
+                    .filter_map(|profile| profile.as_str())
+                    .map(|s| s.to_string())
+                    .collect())
+            }
+        }
+    }
+
+    // Get current profile
+    pub async fn get_current_profile(&self, connection_name: &str) -> Result<String, String> {
+        let (request_type, field) = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => ("GetCurrentProfile", "profile-name"),
+            ObsWebSocketVersion::V5 => ("GetProfileList", "currentProfileName"),
+        };
+
+        let response = self.send_request(connection_name, request_type, None).await?;
+        response[field]
+            .as_str()
+            .ok_or_else(|| "Invalid response format".to_string())
+            .map(|s| s.to_string())
+    }
+
+    // Switch profile after checking it exists, returning the active profile
+    pub async fn set_current_profile(&self, connection_name: &str, profile_name: &str) -> Result<String, String> {
+        let profiles = self.get_profiles(connection_name).await?;
+        if !profiles.iter().any(|p| p == profile_name) {
+            return Err(format!("Profile '{}' not found", profile_name));
+        }
+
+        let request_data = match self.get_protocol_version(connection_name)? {
+            ObsWebSocketVersion::V4 => serde_json::json!({ "profile-name": profile_name }),
+            ObsWebSocketVersion::V5 => serde_json::json!({ "profileName": profile_name }),
+        };
+        self.send_request(connection_name, "SetCurrentProfile", Some(request_data)).await?;
+
+        let active = self.get_current_profile(connection_name).await?;
+
+        let _ = self.event_tx.send(ObsEvent::ProfileChanged {
+            connection_name: connection_name.to_string(),
+            profile_name: active.clone(),
+        });
+
+        Ok(active)
+    }
+
+    // Helper methods
+    fn generate_request_id(&self, connection: &mut ObsConnection) -> String {
+        connection.request_id_counter += 1;
+        Uuid::new_v4().to_string()
+    }
+
+    fn get_protocol_version(&self, connection_name: &str) -> Result<ObsWebSocketVersion, String> {
+        let connections = self.connections.lock().unwrap();
+        let connection = connections.get(connection_name)
+            .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+        
+        Ok(connection.config.protocol_version)
+    }
+
+    // Whether OBS runs on this machine, so its paths are our paths
+    pub fn is_local_connection(&self, connection_name: &str) -> Result<bool, String> {
+        let connections = self.connections.lock().unwrap();
+        let connection = connections.get(connection_name)
+            .ok_or_else(|| format!("Connection '{}' not found", connection_name))?;
+
+        Ok(is_loopback_host(&connection.config.host))
+    }
+
+    // Get connection status
+    pub fn get_connection_status(&self, connection_name: &str) -> Option<ObsConnectionStatus> {
+        let connections = self.connections.lock().unwrap();
+        connections.get(connection_name).map(|c| c.status.clone())
+    }
+
+    // Get latest measured ping
+    pub fn get_last_ping(&self, connection_name: &str) -> Option<u64> {
+        let connections = self.connections.lock().unwrap();
+        connections.get(connection_name).and_then(|c| c.last_ping_ms)
+    }
+
+    // Get the role of a connection
+    pub fn get_connection_role(&self, connection_name: &str) -> Option<ObsConnectionRole> {
+        let connections = self.connections.lock().unwrap();
+        connections.get(connection_name).map(|c| c.config.effective_role())
+    }
+
+    // Get the names of connections holding a role: explicit roles first,
+    // then by name for a stable pick
+    pub fn get_connections_by_role(&self, role: ObsConnectionRole) -> Vec<String> {
+        let connections = self.connections.lock().unwrap();
+        let mut matching: Vec<(bool, String)> = connections.values()
+            .filter(|c| c.config.effective_role() == role)
+            .map(|c| (c.config.role.is_none(), c.config.name.clone()))
+            .collect();
+        matching.sort();
+        matching.into_iter().map(|(_, name)| name).collect()
+    }
+
+    // Get the connection the automatic flows should use for a role
+    pub fn get_connection_for_role(&self, role: ObsConnectionRole) -> Result<String, String> {
+        self.get_connections_by_role(role)
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("No connection has the {:?} role", role))
+    }
+
+    // Get all connection names
+    pub fn get_connection_names(&self) -> Vec<String> {
+        let connections = self.connections.lock().unwrap();
+        connections.keys().cloned().collect()
+    }
+
+    // Remove connection
+    pub fn remove_connection(&self, connection_name: &str) -> Result<(), String> {
+        let mut connections = self.connections.lock().unwrap();
+        
+        if let Some(connection) = connections.remove(connection_name) {
+            for task in [connection.stream_health_monitor, connection.recording_progress_listener].into_iter().flatten() {
+                task.abort();
+            }
+            Ok(())
+        } else {
+            Err(format!("Connection '{}' not found", connection_name))
+        }
+    }
+}
+
+// Validate stream service settings and build the OBS settings object
+fn build_stream_service_settings(service: &str, server: &str, key: &str) -> Result<(&'static str, serde_json::Value), String> {
+    let service = service.trim();
+    let server = server.trim();
+    let key = key.trim();
+
+    if service.is_empty() {
+        return Err("Stream service must not be empty".to_string());
+    }
+    if server.is_empty() {
+        return Err("Stream server must not be empty".to_string());
+    }
+    if key.is_empty() {
+        return Err("Stream key must not be empty".to_string());
+    }
+
+    if service.eq_ignore_ascii_case("custom") {
+        if !(server.starts_with("rtmp://") || server.starts_with("rtmps://")) {
+            return Err(format!("Custom stream server '{}' must be an rtmp:// or rtmps:// URL", server));
+        }
+        Ok(("rtmp_custom", serde_json::json!({ "server": server, "key": key })))
+    } else {
+        Ok(("rtmp_common", serde_json::json!({ "service": service, "server": server, "key": key })))
+    }
+}
+
+// Show only the last 4 characters of a stream key
+pub fn mask_stream_key(key: &str) -> String {
+    let key: Vec<char> = key.trim().chars().collect();
+    if key.len() <= 4 {
+        "****".to_string()
+    } else {
+        format!("****{}", key[key.len() - 4..].iter().collect::<String>())
+    }
+}
+
+// Name of the device list property for common capture input kinds
+fn device_property_for_input_kind(input_kind: &str) -> &'static str {
+    match input_kind {
+        "dshow_input" => "video_device_id",
+        "av_capture_input" | "av_capture_input_v2" | "macos_avcapture" => "device",
+        "decklink-input" => "device_hash",
+        _ => "device_id",
+    }
+}
+
+// Drop control characters (except newlines) and cap the length
+fn sanitize_text_source(text: &str) -> String {
+    text.chars()
+        .filter(|c| *c == '\n' || !c.is_control())
+        .take(MAX_TEXT_SOURCE_LENGTH)
+        .collect()
+}
+
+// Text sources are text_gdiplus(_v2/_v3) on Windows and text_ft2_source(_v2) elsewhere
+fn validate_text_source_kind(source_name: &str, kind: Option<&str>) -> Result<(), String> {
+    match kind {
+        Some(kind) if kind.starts_with("text_") => Ok(()),
+        Some(kind) => Err(format!("Source '{}' is not a text source (kind '{}')", source_name, kind)),
+        None => Err(format!("Source '{}' not found", source_name)),
+    }
+}
+
+fn unix_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// "HH:MM:SS.mmm" -> milliseconds
+fn parse_timecode_ms(timecode: &str) -> Option<u64> {
+    let (hms, millis) = timecode.split_once('.').unwrap_or((timecode, "0"));
+    let mut parts = hms.split(':').map(|p| p.parse::<u64>().ok());
+    let hours = parts.next()??;
+    let minutes = parts.next()??;
+    let seconds = parts.next()??;
+    let millis = millis.parse::<u64>().ok()?;
+
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis)
+}
+
+// (skipped, total) frames since the previous sample; everything if there is
+// none or the counters were reset by a new stream
+fn recent_frames(previous: Option<(u64, u64)>, skipped_frames: u64, total_frames: u64) -> (u64, u64) {
+    match previous {
+        Some((prev_skipped, prev_total)) if total_frames >= prev_total => {
+            (skipped_frames.saturating_sub(prev_skipped), total_frames - prev_total)
+        }
+        _ => (skipped_frames, total_frames),
+    }
+}
+
+fn frame_drop_ratio(skipped: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        skipped as f64 / total as f64
+    }
+}
+
+type ObsWebSocketStream = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+// A message received from OBS, keyed by what the reader has to do with it
+enum ObsMessage {
+    Response { request_id: String, response: serde_json::Value },
+    Event { event_type: String, data: serde_json::Value },
+    Other,
+}
+
+fn parse_obs_message(protocol_version: ObsWebSocketVersion, message: serde_json::Value) -> ObsMessage {
+    match protocol_version {
+        ObsWebSocketVersion::V4 => {
+            if let Some(request_id) = message["message-id"].as_str() {
+                ObsMessage::Response { request_id: request_id.to_string(), response: message }
+            } else if let Some(event_type) = message["update-type"].as_str() {
+                ObsMessage::Event { event_type: event_type.to_string(), data: message }
+            } else {
+                ObsMessage::Other
+            }
+        }
+        ObsWebSocketVersion::V5 => {
+            let d = &message["d"];
+            match message["op"].as_u64() {
+                // RequestResponse
+                Some(7) => match d["requestId"].as_str() {
+                    Some(request_id) => ObsMessage::Response { request_id: request_id.to_string(), response: d.clone() },
+                    None => ObsMessage::Other,
+                },
+                // Event
+                Some(5) => match d["eventType"].as_str() {
+                    Some(event_type) => ObsMessage::Event {
+                        event_type: event_type.to_string(),
+                        data: d["eventData"].clone(),
+                    },
+                    None => ObsMessage::Other,
+                },
+                _ => ObsMessage::Other,
+            }
+        }
+    }
+}
+
+// Create request based on protocol version
+fn build_request(
+    protocol_version: ObsWebSocketVersion,
+    request_id: &str,
+    request_type: &str,
+    request_data: Option<serde_json::Value>,
+) -> serde_json::Value {
+    match protocol_version {
+        ObsWebSocketVersion::V4 => {
+            // v4 takes request fields next to the request type
+            let mut request = match request_data {
+                Some(serde_json::Value::Object(fields)) => serde_json::Value::Object(fields),
+                _ => serde_json::json!({}),
+            };
+            request["request-type"] = serde_json::json!(request_type);
+            request["message-id"] = serde_json::json!(request_id);
+            request
+        }
+        ObsWebSocketVersion::V5 => {
+            serde_json::json!({
+                "op": 6, // Request opcode
+                "d": {
+                    "requestType": request_type,
+                    "requestId": request_id,
+                    "requestData": request_data
+                }
+            })
+        }
+    }
+}
+
+// Turn a raw response into its data or an error. v4 data sits next to the
+// status fields; v5 data is in responseData.
+fn request_result(
+    protocol_version: ObsWebSocketVersion,
+    request_type: &str,
+    response: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    if is_unsupported_request(&response) {
+        return Err(format!("'{}' is not supported by this OBS version", request_type));
+    }
+
+    match protocol_version {
+        ObsWebSocketVersion::V4 => {
+            if response["status"] == "error" {
+                return Err(format!(
+                    "{} failed: {}",
+                    request_type,
+                    response["error"].as_str().unwrap_or("unknown error")
+                ));
+            }
+            Ok(response)
+        }
+        ObsWebSocketVersion::V5 => {
+            let status = &response["requestStatus"];
+            if status["result"].as_bool() != Some(true) {
+                let reason = status["comment"]
+                    .as_str()
+                    .map(|comment| comment.to_string())
+                    .unwrap_or_else(|| format!("status code {}", status["code"]));
+                return Err(format!("{} failed: {}", request_type, reason));
+            }
+            Ok(response.get("responseData").cloned().unwrap_or_else(|| serde_json::json!({})))
+        }
+    }
+}
+
+// v5 output events report intermediate STARTING/STOPPING states too; only
+// the settled started/stopped states change anything
+fn output_state_settled(data: &serde_json::Value) -> Option<bool> {
+    match data["outputState"].as_str()? {
+        "OBS_WEBSOCKET_OUTPUT_STARTED" => Some(true),
+        "OBS_WEBSOCKET_OUTPUT_STOPPED" => Some(false),
+        _ => None,
+    }
+}
+
+fn virtual_camera_error(error: String) -> String {
+    if error.contains("not supported") {
+        "Virtual camera is not supported by this OBS version".to_string()
+    } else {
+        error
+    }
+}
+
+fn connect_error(host: &str, port: u16, error: tungstenite::Error) -> String {
+    match error {
+        tungstenite::Error::Io(io) if io.kind() == std::io::ErrorKind::ConnectionRefused => {
+            format!("Connection refused by {}:{} (is obs-websocket enabled?)", host, port)
+        }
+        other => format!("Failed to connect to OBS: {}", other),
+    }
+}
+
+// obs-websocket auth string: base64(sha256(base64(sha256(password + salt)) + challenge))
+fn obs_auth_response(password: &str, salt: &str, challenge: &str) -> String {
+    let base64 = base64::engine::general_purpose::STANDARD;
+    let secret = base64.encode(Sha256::digest(format!("{}{}", password, salt)));
+    base64.encode(Sha256::digest(format!("{}{}", secret, challenge)))
+}
+
+fn required_password(password: Option<&str>) -> Result<&str, String> {
+    password
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| "OBS requires a password".to_string())
+}
+
+// Log in on a fresh v4 connection
+async fn handshake_v4(ws_stream: &mut ObsWebSocketStream, password: Option<&str>) -> Result<ObsConnectionTestResult, String> {
+    let version = handshake_request_v4(ws_stream, "GetVersion", None).await?;
+    let auth = handshake_request_v4(ws_stream, "GetAuthRequired", None).await?;
+    let authentication_required = auth["authRequired"].as_bool().unwrap_or(false);
+
+    if authentication_required {
+        let password = required_password(password)?;
+        let auth_response = obs_auth_response(
+            password,
+            auth["salt"].as_str().unwrap_or(""),
+            auth["challenge"].as_str().unwrap_or(""),
+        );
+
+        let response = handshake_request_v4(ws_stream, "Authenticate", Some(serde_json::json!({
+            "auth": auth_response
+        }))).await?;
+        if response["status"] == "error" {
+            return Err("Authentication failed: wrong password".to_string());
+        }
+    }
+
+    Ok(ObsConnectionTestResult {
+        obs_version: version["obs-studio-version"].as_str().map(|s| s.to_string()),
+        websocket_version: version["obs-websocket-version"].as_str().map(|s| s.to_string()),
+        authentication_required,
+    })
+}
+
+// Hello -> Identify -> Identified on a fresh v5 connection
+async fn handshake_v5(ws_stream: &mut ObsWebSocketStream, password: Option<&str>) -> Result<ObsConnectionTestResult, String> {
+    // Server sends Hello (op 0) immediately after the upgrade
+    let hello = read_handshake_message(ws_stream).await?;
+    if hello["op"] != 0 {
+        return Err("Unexpected first message from OBS (is this a v4 server?)".to_string());
+    }
+
+    let authentication = &hello["d"]["authentication"];
+    let authentication_required = !authentication.is_null();
+
+    let mut identify = serde_json::json!({ "rpcVersion": OBS_RPC_VERSION });
+    if authentication_required {
+        let password = required_password(password)?;
+        identify["authentication"] = serde_json::json!(obs_auth_response(
+            password,
+            authentication["salt"].as_str().unwrap_or(""),
+            authentication["challenge"].as_str().unwrap_or(""),
+        ));
+    }
+
+    ws_stream.send(Message::Text(serde_json::json!({ "op": 1, "d": identify }).to_string()))
+        .await
+        .map_err(|e| format!("Failed to send Identify to OBS: {}", e))?;
+
+    // A wrong password closes the socket with 4009 instead of answering
+    let identified = read_handshake_message(ws_stream).await?;
+    if identified["op"] != 2 {
+        return Err("Unexpected reply to Identify from OBS".to_string());
+    }
+
+    let version = handshake_request_v5(ws_stream, "GetVersion").await?;
+
+    Ok(ObsConnectionTestResult {
+        obs_version: version["obsVersion"].as_str().map(|s| s.to_string()),
+        websocket_version: hello["d"]["obsWebSocketVersion"].as_str().map(|s| s.to_string()),
+        authentication_required,
+    })
+}
+
+// Send a v4 request during the handshake and wait for its reply
+async fn handshake_request_v4(
+    ws_stream: &mut ObsWebSocketStream,
+    request_type: &str,
+    request_data: Option<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    let request_id = Uuid::new_v4().to_string();
+    let request = build_request(ObsWebSocketVersion::V4, &request_id, request_type, request_data);
+
+    ws_stream.send(Message::Text(request.to_string()))
+        .await
+        .map_err(|e| format!("Failed to send {} to OBS: {}", request_type, e))?;
+
+    loop {
+        let message = read_handshake_message(ws_stream).await?;
+        if message["message-id"] == request_id.as_str() {
+            return Ok(message);
+        }
+    }
+}
+
+// Send a v5 request during the handshake and wait for its response data
+async fn handshake_request_v5(ws_stream: &mut ObsWebSocketStream, request_type: &str) -> Result<serde_json::Value, String> {
+    let request_id = Uuid::new_v4().to_string();
+    let request = build_request(ObsWebSocketVersion::V5, &request_id, request_type, None);
+
+    ws_stream.send(Message::Text(request.to_string()))
+        .await
+        .map_err(|e| format!("Failed to send {} to OBS: {}", request_type, e))?;
+
+    loop {
+        let message = read_handshake_message(ws_stream).await?;
+        if let ObsMessage::Response { request_id: id, response } = parse_obs_message(ObsWebSocketVersion::V5, message) {
+            if id == request_id {
+                return request_result(ObsWebSocketVersion::V5, request_type, response);
+            }
+        }
+    }
+}
+
+// Read the next JSON text message while connecting
+async fn read_handshake_message(ws_stream: &mut ObsWebSocketStream) -> Result<serde_json::Value, String> {
+    loop {
+        let message = tokio::time::timeout(HANDSHAKE_TIMEOUT, ws_stream.next())
+            .await
+            .map_err(|_| "Timed out waiting for a response from OBS".to_string())?
+            .ok_or_else(|| "OBS closed the connection".to_string())?
+            .map_err(|e| format!("Failed to read from OBS: {}", e))?;
+
+        match message {
+            Message::Text(text) => {
+                return serde_json::from_str(&text).map_err(|e| format!("Invalid message from OBS: {}", e));
+            }
+            Message::Close(Some(frame)) if frame.code == CloseCode::from(CLOSE_CODE_AUTHENTICATION_FAILED) => {
+                return Err("Authentication failed: wrong password".to_string());
+            }
+            Message::Close(Some(frame)) => {
+                return Err(format!("OBS closed the connection ({}): {}", u16::from(frame.code), frame.reason));
+            }
+            Message::Close(None) => return Err("OBS closed the connection".to_string()),
+            _ => continue,
+        }
+    }
+}
+
+// Simple output mode keeps a separate preset per encoder family
+fn simple_output_preset_key(encoder: &str) -> Option<&'static str> {
+    match encoder {
+        "x264" | "x264_lowcpu" => Some("Preset"),
+        _ if encoder.starts_with("nvenc") => Some("NVENCPreset2"),
+        _ if encoder.starts_with("qsv") => Some("QSVPreset"),
+        _ if encoder.starts_with("amd") => Some("AMDPreset"),
+        _ => None,
+    }
+}
+
+// Set one option in an x264 "key=value key=value" option string, keeping the others
+fn with_x264_option(options: &str, key: &str, value: &str) -> String {
+    let prefix = format!("{}=", key);
+    options.split_whitespace()
+        .filter(|option| !option.starts_with(&prefix))
+        .map(|option| option.to_string())
+        .chain(std::iter::once(format!("{}{}", prefix, value)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Where quality profiles are saved: the app's config folder
+pub fn default_quality_profiles_path() -> Option<PathBuf> {
+    let config_dir = if cfg!(windows) {
+        PathBuf::from(std::env::var_os("APPDATA")?).join("reStrike VTA")
+    } else {
+        PathBuf::from(std::env::var_os("HOME")?).join(".config").join("restrike-vta")
+    };
+
+    Some(config_dir.join("quality_profiles.json"))
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<std::net::IpAddr>().map(|ip| ip.is_loopback()).unwrap_or(false)
+}
+
+// Default recording directory: the user's Videos folder
+pub fn default_recording_directory() -> Option<PathBuf> {
+    let home = if cfg!(windows) {
+        std::env::var_os("USERPROFILE")
+    } else {
+        std::env::var_os("HOME")
+    }?;
+
+    Some(PathBuf::from(home).join("Videos"))
+}
+
+// Create the recording directory if missing and check we can write to it
+pub fn validate_recording_directory(directory: &Path) -> Result<PathBuf, String> {
+    if directory.as_os_str().is_empty() {
+        return Err("Recording path must not be empty".to_string());
+    }
+    if !directory.is_absolute() {
+        return Err(format!("Recording path '{}' must be absolute", directory.display()));
+    }
+    if directory.exists() && !directory.is_dir() {
+        return Err(format!("Recording path '{}' is not a directory", directory.display()));
+    }
+
+    std::fs::create_dir_all(directory).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            format!("Permission denied creating recording path '{}'", directory.display())
+        }
+        _ => format!("Invalid recording path '{}': {}", directory.display(), e),
+    })?;
+
+    let probe = directory.join(format!(".restrike_write_test_{}", Uuid::new_v4()));
+    std::fs::write(&probe, b"").map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            format!("Recording path '{}' is not writable (permission denied)", directory.display())
+        }
+        _ => format!("Recording path '{}' is not writable: {}", directory.display(), e),
+    })?;
+    let _ = std::fs::remove_file(&probe);
+
+    Ok(directory.to_path_buf())
+}
+
+// Most recently modified regular file in a directory
+fn newest_file_in(directory: &Path) -> Option<String> {
+    std::fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            Some((metadata.modified().ok()?, entry.path()))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path.to_string_lossy().to_string())
+}
+
+// Whether OBS rejected a request because it doesn't know the request type
+// (v4 replies with an "invalid request type" error, v5 with status code 204)
+fn is_unsupported_request(response: &serde_json::Value) -> bool {
+    let v4_unsupported = response["status"] == "error"
+        && response["error"]
+            .as_str()
+            .map(|e| e.to_lowercase().contains("invalid request type"))
+            .unwrap_or(false);
+    let v5_unsupported = response["requestStatus"]["code"] == 204;
+
+    v4_unsupported || v5_unsupported
+}
+
+// Legacy function for backward compatibility
 pub fn connect_obs() {
     println!("OBS WebSocket plugin initialized with dual-protocol support");
     println!("Use ObsPlugin::new() to create a plugin instance");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio::sync::broadcast;
+    use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+
+    const MOCK_SALT: &str = "lM1GncleQOaCu9lT1yeUZhFYnqhsLLP1G5lAGo3ixaI=";
+    const MOCK_CHALLENGE: &str = "+IxH4CnCiqpX1rM9scsNynZzbOe4KhDeYcTNS3PDaeY=";
+
+    // What the mock OBS does with a request
+    enum MockReply {
+        Ok(serde_json::Value),
+        Fail(u64, &'static str),
+        // Answer after a delay without holding up other requests or events
+        Delayed(Duration, serde_json::Value),
+        Silent,
+    }
+
+    type MockHandler = Arc<dyn Fn(&str, &serde_json::Value) -> MockReply + Send + Sync>;
+
+    // Minimal obs-websocket v4/v5 server answering requests with a handler
+    struct MockObs {
+        port: u16,
+        protocol_version: ObsWebSocketVersion,
+        events: broadcast::Sender<String>,
+        requests: Arc<Mutex<Vec<(String, serde_json::Value)>>>,
+        // While set, requests go unanswered and new connections are dropped
+        silent: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl MockObs {
+        async fn start<F>(password: Option<&'static str>, handler: F) -> Self
+        where
+            F: Fn(&str, &serde_json::Value) -> MockReply + Send + Sync + 'static,
+        {
+            Self::start_with(ObsWebSocketVersion::V5, password, handler).await
+        }
+
+        // v4 mock; never asks for a password
+        async fn start_v4<F>(handler: F) -> Self
+        where
+            F: Fn(&str, &serde_json::Value) -> MockReply + Send + Sync + 'static,
+        {
+            Self::start_with(ObsWebSocketVersion::V4, None, handler).await
+        }
+
+        async fn start_with<F>(protocol_version: ObsWebSocketVersion, password: Option<&'static str>, handler: F) -> Self
+        where
+            F: Fn(&str, &serde_json::Value) -> MockReply + Send + Sync + 'static,
+        {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let (events, _) = broadcast::channel(16);
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let silent = Arc::new(std::sync::atomic::AtomicBool::new(false));
+            let handler: MockHandler = Arc::new(handler);
+
+            let server_events = events.clone();
+            let server_requests = requests.clone();
+            let server_silent = silent.clone();
+            tokio::spawn(async move {
+                while let Ok((tcp, _)) = listener.accept().await {
+                    if server_silent.load(std::sync::atomic::Ordering::SeqCst) {
+                        continue;
+                    }
+                    let ws_stream = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                    tokio::spawn(serve_mock_client(
+                        ws_stream,
+                        protocol_version,
+                        password,
+                        handler.clone(),
+                        server_requests.clone(),
+                        server_events.subscribe(),
+                        server_silent.clone(),
+                    ));
+                }
+            });
+
+            Self { port, protocol_version, events, requests, silent }
+        }
+
+        fn set_silent(&self, silent: bool) {
+            self.silent.store(silent, std::sync::atomic::Ordering::SeqCst);
+        }
+
+        fn emit(&self, event_type: &str, event_data: serde_json::Value) {
+            let event = match self.protocol_version {
+                ObsWebSocketVersion::V4 => {
+                    let mut event = event_data;
+                    event["update-type"] = serde_json::json!(event_type);
+                    event
+                }
+                ObsWebSocketVersion::V5 => serde_json::json!({
+                    "op": 5,
+                    "d": { "eventType": event_type, "eventIntent": 0, "eventData": event_data }
+                }),
+            };
+            let _ = self.events.send(event.to_string());
+        }
+
+        fn requests_of(&self, request_type: &str) -> Vec<serde_json::Value> {
+            self.requests.lock().unwrap().iter()
+                .filter(|(t, _)| t == request_type)
+                .map(|(_, data)| data.clone())
+                .collect()
+        }
+
+        // Wait until the plugin has sent `count` requests of a type
+        async fn wait_for_requests(&self, request_type: &str, count: usize) -> Vec<serde_json::Value> {
+            for _ in 0..100 {
+                let requests = self.requests_of(request_type);
+                if requests.len() >= count {
+                    return requests;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            self.requests_of(request_type)
+        }
+    }
+
+    async fn serve_mock_client(
+        ws_stream: tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
+        protocol_version: ObsWebSocketVersion,
+        password: Option<&'static str>,
+        handler: MockHandler,
+        requests: Arc<Mutex<Vec<(String, serde_json::Value)>>>,
+        mut events: broadcast::Receiver<String>,
+        silent: Arc<std::sync::atomic::AtomicBool>,
+    ) {
+        let (mut sink, mut stream) = ws_stream.split();
+        let (out_tx, mut out_rx) = mpsc::unbounded_channel::<Message>();
+        tokio::spawn(async move {
+            while let Some(message) = out_rx.recv().await {
+                if sink.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        if protocol_version == ObsWebSocketVersion::V5 {
+            let mut hello = serde_json::json!({ "obsWebSocketVersion": "5.4.2", "rpcVersion": 1 });
+            if password.is_some() {
+                hello["authentication"] = serde_json::json!({ "challenge": MOCK_CHALLENGE, "salt": MOCK_SALT });
+            }
+            let _ = out_tx.send(Message::Text(serde_json::json!({ "op": 0, "d": hello }).to_string()));
+
+            let Some(Ok(Message::Text(identify))) = stream.next().await else {
+                return;
+            };
+            let identify: serde_json::Value = serde_json::from_str(&identify).unwrap();
+            if let Some(password) = password {
+                if identify["d"]["authentication"] != obs_auth_response(password, MOCK_SALT, MOCK_CHALLENGE).as_str() {
+                    let _ = out_tx.send(Message::Close(Some(CloseFrame {
+                        code: CloseCode::from(CLOSE_CODE_AUTHENTICATION_FAILED),
+                        reason: "Authentication failed.".into(),
+                    })));
+                    return;
+                }
+            }
+            let _ = out_tx.send(Message::Text(serde_json::json!({ "op": 2, "d": { "negotiatedRpcVersion": 1 } }).to_string()));
+        }
+
+        loop {
+            tokio::select! {
+                message = stream.next() => {
+                    let Some(Ok(message)) = message else { break };
+                    let Message::Text(text) = message else { continue };
+                    let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                    let (request_type, request_id, request_data) = match protocol_version {
+                        ObsWebSocketVersion::V4 => {
+                            let mut fields = request.clone();
+                            if let Some(fields) = fields.as_object_mut() {
+                                fields.remove("request-type");
+                                fields.remove("message-id");
+                            }
+                            (request["request-type"].clone(), request["message-id"].clone(), fields)
+                        }
+                        ObsWebSocketVersion::V5 => {
+                            let d = &request["d"];
+                            (d["requestType"].clone(), d["requestId"].clone(), d["requestData"].clone())
+                        }
+                    };
+                    let request_type = request_type.as_str().unwrap_or("").to_string();
+                    requests.lock().unwrap().push((request_type.clone(), request_data.clone()));
+
+                    let reply = match request_type.as_str() {
+                        _ if silent.load(std::sync::atomic::Ordering::SeqCst) => MockReply::Silent,
+                        "GetAuthRequired" => MockReply::Ok(serde_json::json!({ "authRequired": false })),
+                        _ => handler(&request_type, &request_data),
+                    };
+                    let (delay, reply) = match reply {
+                        MockReply::Delayed(delay, data) => (Some(delay), Ok(data)),
+                        MockReply::Ok(data) => (None, Ok(data)),
+                        MockReply::Fail(code, comment) => (None, Err((code, comment))),
+                        MockReply::Silent => continue,
+                    };
+                    let response = mock_response(protocol_version, &request_type, request_id, reply);
+
+                    match delay {
+                        Some(delay) => {
+                            let out_tx = out_tx.clone();
+                            tokio::spawn(async move {
+                                tokio::time::sleep(delay).await;
+                                let _ = out_tx.send(Message::Text(response.to_string()));
+                            });
+                        }
+                        None => {
+                            if out_tx.send(Message::Text(response.to_string())).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+                event = events.recv() => {
+                    let Ok(event) = event else { break };
+                    if out_tx.send(Message::Text(event)).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn mock_response(
+        protocol_version: ObsWebSocketVersion,
+        request_type: &str,
+        request_id: serde_json::Value,
+        reply: Result<serde_json::Value, (u64, &str)>,
+    ) -> serde_json::Value {
+        match protocol_version {
+            ObsWebSocketVersion::V4 => {
+                let mut response = match reply {
+                    Ok(serde_json::Value::Object(fields)) => {
+                        let mut response = serde_json::Value::Object(fields);
+                        response["status"] = serde_json::json!("ok");
+                        response
+                    }
+                    Ok(_) => serde_json::json!({ "status": "ok" }),
+                    Err((_, comment)) => serde_json::json!({ "status": "error", "error": comment }),
+                };
+                response["message-id"] = request_id;
+                response
+            }
+            ObsWebSocketVersion::V5 => {
+                let (result, code, comment, data) = match reply {
+                    Ok(data) => (true, 100, None, data),
+                    Err((code, comment)) => (false, code, Some(comment), serde_json::Value::Null),
+                };
+                serde_json::json!({
+                    "op": 7,
+                    "d": {
+                        "requestType": request_type,
+                        "requestId": request_id,
+                        "requestStatus": { "result": result, "code": code, "comment": comment },
+                        "responseData": data
+                    }
+                })
+            }
+        }
+    }
+
+    fn test_config(name: &str, port: u16) -> ObsConnectionConfig {
+        ObsConnectionConfig {
+            name: name.to_string(),
+            host: "127.0.0.1".to_string(),
+            port,
+            password: None,
+            protocol_version: ObsWebSocketVersion::V5,
+            enabled: true,
+            recording_indicator_source: None,
+            scene_collection: None,
+            auto_start_replay_buffer: false,
+            pause_recording_on_break: false,
+            role: None,
+        }
+    }
+
+    async fn connected_plugin(mock: &MockObs) -> ObsPlugin {
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let config = ObsConnectionConfig {
+            protocol_version: mock.protocol_version,
+            ..test_config("OBS", mock.port)
+        };
+        plugin.add_connection(config).await.unwrap();
+        plugin
+    }
+
+    #[tokio::test]
+    async fn requests_are_sent_and_answered() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetCurrentProgramScene" => MockReply::Ok(serde_json::json!({ "sceneName": "Court 1" })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert_eq!(plugin.get_current_scene("OBS").await.unwrap(), "Court 1");
+        assert!(plugin.ping("OBS").await.is_ok());
+        assert!(plugin.get_last_ping("OBS").is_some());
+    }
+
+    #[tokio::test]
+    async fn failed_requests_return_the_obs_comment() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "SetCurrentProgramScene" => MockReply::Fail(600, "No source was found by the name of `Missing`."),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let error = plugin.set_current_scene("OBS", "Missing").await.unwrap_err();
+        assert!(error.contains("No source was found"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn timed_out_requests_are_not_left_pending() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetStats" => MockReply::Silent,
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let result = plugin
+            .send_request_with_timeout("OBS", "GetStats", None, Duration::from_millis(100))
+            .await;
+
+        assert!(result.unwrap_err().contains("did not respond"));
+        assert!(plugin.connections.lock().unwrap()["OBS"].pending_requests.is_empty());
+    }
+
+    #[test]
+    fn v4_requests_carry_fields_next_to_the_request_type() {
+        let request = build_request(
+            ObsWebSocketVersion::V4,
+            "1",
+            "SetCurrentScene",
+            Some(serde_json::json!({ "scene-name": "Court 1" })),
+        );
+
+        assert_eq!(request["request-type"], "SetCurrentScene");
+        assert_eq!(request["message-id"], "1");
+        assert_eq!(request["scene-name"], "Court 1");
+    }
+
+    #[test]
+    fn messages_are_classified_by_protocol() {
+        let v4_response = serde_json::json!({ "message-id": "1", "status": "ok", "scene-name": "Court 1" });
+        assert!(matches!(
+            parse_obs_message(ObsWebSocketVersion::V4, v4_response),
+            ObsMessage::Response { request_id, .. } if request_id == "1"
+        ));
+
+        let v4_event = serde_json::json!({ "update-type": "SwitchScenes", "scene-name": "Court 1" });
+        assert!(matches!(
+            parse_obs_message(ObsWebSocketVersion::V4, v4_event),
+            ObsMessage::Event { event_type, .. } if event_type == "SwitchScenes"
+        ));
+
+        let v5_event = serde_json::json!({
+            "op": 5,
+            "d": { "eventType": "CurrentProgramSceneChanged", "eventData": { "sceneName": "Court 1" } }
+        });
+        match parse_obs_message(ObsWebSocketVersion::V5, v5_event) {
+            ObsMessage::Event { event_type, data } => {
+                assert_eq!(event_type, "CurrentProgramSceneChanged");
+                assert_eq!(data["sceneName"], "Court 1");
+            }
+            _ => panic!("expected an event"),
+        }
+
+        let v5_identified = serde_json::json!({ "op": 2, "d": { "negotiatedRpcVersion": 1 } });
+        assert!(matches!(parse_obs_message(ObsWebSocketVersion::V5, v5_identified), ObsMessage::Other));
+    }
+
+    #[test]
+    fn request_results_unwrap_data_or_report_errors() {
+        let v4_error = serde_json::json!({ "message-id": "1", "status": "error", "error": "scene does not exist" });
+        assert_eq!(
+            request_result(ObsWebSocketVersion::V4, "SetCurrentScene", v4_error).unwrap_err(),
+            "SetCurrentScene failed: scene does not exist"
+        );
+
+        let v5_ok = serde_json::json!({
+            "requestId": "1",
+            "requestStatus": { "result": true, "code": 100 },
+            "responseData": { "sceneName": "Court 1" }
+        });
+        assert_eq!(
+            request_result(ObsWebSocketVersion::V5, "GetCurrentProgramScene", v5_ok).unwrap()["sceneName"],
+            "Court 1"
+        );
+
+        let v5_no_data = serde_json::json!({ "requestId": "1", "requestStatus": { "result": true, "code": 100 } });
+        assert!(request_result(ObsWebSocketVersion::V5, "StartRecord", v5_no_data).unwrap().is_object());
+    }
+
+    #[tokio::test]
+    async fn ping_measures_the_round_trip_time() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetVersion" => MockReply::Delayed(Duration::from_millis(100), serde_json::json!({})),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let latency_ms = plugin.ping("OBS").await.unwrap();
+
+        assert!((100..PING_TIMEOUT.as_millis() as u64).contains(&latency_ms), "{}", latency_ms);
+        assert_eq!(plugin.get_last_ping("OBS"), Some(latency_ms));
+    }
+
+    #[tokio::test]
+    async fn ping_fails_fast_when_obs_does_not_answer() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetVersion" => MockReply::Delayed(Duration::from_millis(200), serde_json::json!({})),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let error = plugin.ping_with_timeout("OBS", Duration::from_millis(50)).await.unwrap_err();
+        assert!(error.contains("did not respond"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn bulk_operations_continue_past_failing_connections() {
+        let healthy = MockObs::start(None, |_, _| MockReply::Ok(serde_json::json!({}))).await;
+        let failing = MockObs::start(None, |request_type, _| match request_type {
+            "StartRecord" => MockReply::Fail(500, "Recording is already active."),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        plugin.add_connection(test_config("OBS_A", healthy.port)).await.unwrap();
+        plugin.add_connection(test_config("OBS_B", failing.port)).await.unwrap();
+
+        let mut results = plugin.start_all_recording().await;
+        results.sort_by(|a, b| a.connection_name.cmp(&b.connection_name));
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert!(results[1].error.as_deref().unwrap().contains("already active"));
+    }
+
+    #[tokio::test]
+    async fn bulk_operations_skip_unauthenticated_connections() {
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let mut config = test_config("OBS", 1);
+        config.enabled = false;
+        plugin.add_connection(config).await.unwrap();
+
+        assert!(plugin.change_all_scenes("Court 1").await.is_empty());
+    }
+
+    #[test]
+    fn scene_item_transforms_are_clamped() {
+        let transform = SceneItemTransform {
+            x: 50_000.0,
+            y: -50_000.0,
+            scale_x: 0.0,
+            scale_y: 500.0,
+            rotation: -90.0,
+        };
+
+        let clamped = transform.clamped();
+
+        assert_eq!(clamped.x, MAX_SCENE_ITEM_POSITION);
+        assert_eq!(clamped.y, -MAX_SCENE_ITEM_POSITION);
+        assert_eq!(clamped.scale_x, MIN_SCENE_ITEM_SCALE);
+        assert_eq!(clamped.scale_y, MAX_SCENE_ITEM_SCALE);
+        assert_eq!(clamped.rotation, 270.0);
+
+        let in_range = SceneItemTransform { x: 100.0, y: 200.0, scale_x: 1.5, scale_y: 1.5, rotation: 45.0 };
+        assert_eq!(in_range.clamped(), in_range);
+    }
+
+    // Mock scene "Court 1" holding only the "Replay" item (id 7)
+    fn scene_item_mock(request_type: &str, data: &serde_json::Value) -> MockReply {
+        match request_type {
+            "GetSceneItemId" if data["sourceName"] == "Replay" => MockReply::Ok(serde_json::json!({ "sceneItemId": 7 })),
+            "GetSceneItemId" => MockReply::Fail(600, "No scene items were found in the specified scene by that name."),
+            "GetSceneItemTransform" => MockReply::Ok(serde_json::json!({ "sceneItemTransform": {
+                "positionX": 1280.0, "positionY": 40.0, "scaleX": 0.5, "scaleY": 0.5, "rotation": 0.0
+            } })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }
+    }
+
+    #[tokio::test]
+    async fn scene_item_transforms_are_read_by_item_id() {
+        let mock = MockObs::start(None, scene_item_mock).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let transform = plugin.get_scene_item_transform("OBS", "Court 1", "Replay").await.unwrap();
+
+        assert_eq!(transform, SceneItemTransform { x: 1280.0, y: 40.0, scale_x: 0.5, scale_y: 0.5, rotation: 0.0 });
+        assert_eq!(mock.requests_of("GetSceneItemTransform")[0]["sceneItemId"], 7);
+    }
+
+    #[tokio::test]
+    async fn scene_item_transforms_are_clamped_before_sending() {
+        let mock = MockObs::start(None, scene_item_mock).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let requested = SceneItemTransform { x: 100.0, y: 50.0, scale_x: 1000.0, scale_y: 0.25, rotation: -90.0 };
+        let applied = plugin.set_scene_item_transform("OBS", "Court 1", "Replay", requested).await.unwrap();
+
+        assert_eq!(applied, requested.clamped());
+        assert_eq!(applied.rotation, 270.0);
+        let sent = &mock.requests_of("SetSceneItemTransform")[0];
+        assert_eq!(sent["sceneName"], "Court 1");
+        assert_eq!(sent["sceneItemId"], 7);
+        assert_eq!(sent["sceneItemTransform"]["scaleX"], MAX_SCENE_ITEM_SCALE);
+        assert_eq!(sent["sceneItemTransform"]["rotation"], 270.0);
+    }
+
+    #[tokio::test]
+    async fn unknown_scene_items_are_rejected() {
+        let mock = MockObs::start(None, scene_item_mock).await;
+        let plugin = connected_plugin(&mock).await;
+        let transform = SceneItemTransform { x: 0.0, y: 0.0, scale_x: 1.0, scale_y: 1.0, rotation: 0.0 };
+
+        let error = plugin.get_scene_item_transform("OBS", "Court 1", "Missing").await.unwrap_err();
+        assert!(error.contains("No scene items were found"), "{}", error);
+        assert!(plugin.set_scene_item_transform("OBS", "Court 1", "Missing", transform).await.is_err());
+        assert!(mock.requests_of("SetSceneItemTransform").is_empty());
+    }
+
+    #[test]
+    fn unknown_request_types_are_detected_for_both_protocols() {
+        let v4 = serde_json::json!({ "status": "error", "error": "invalid request type" });
+        let v5 = serde_json::json!({ "requestStatus": { "result": false, "code": 204 } });
+        let v5_other_failure = serde_json::json!({ "requestStatus": { "result": false, "code": 600 } });
+
+        assert!(is_unsupported_request(&v4));
+        assert!(is_unsupported_request(&v5));
+        assert!(!is_unsupported_request(&v5_other_failure));
+        assert!(!is_unsupported_request(&serde_json::json!({ "status": "ok" })));
+    }
+
+    #[tokio::test]
+    async fn virtual_camera_reports_unsupported_obs() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "StartVirtualCam" => MockReply::Fail(204, "Your request type is not valid."),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert_eq!(
+            plugin.start_virtual_camera("OBS").await.unwrap_err(),
+            "Virtual camera is not supported by this OBS version"
+        );
+    }
+
+    #[tokio::test]
+    async fn virtual_camera_start_and_stop_report_the_resulting_state() {
+        let active = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let mock_active = active.clone();
+        let mock = MockObs::start(None, move |request_type, _| {
+            use std::sync::atomic::Ordering;
+            match request_type {
+                "StartVirtualCam" => mock_active.store(true, Ordering::SeqCst),
+                "StopVirtualCam" => mock_active.store(false, Ordering::SeqCst),
+                "GetVirtualCamStatus" => {
+                    return MockReply::Ok(serde_json::json!({ "outputActive": mock_active.load(Ordering::SeqCst) }));
+                }
+                _ => {}
+            }
+            MockReply::Ok(serde_json::json!({}))
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert!(!plugin.get_virtual_camera_status("OBS").await.unwrap());
+        assert!(plugin.start_virtual_camera("OBS").await.unwrap());
+        assert!(plugin.get_virtual_camera_status("OBS").await.unwrap());
+        assert!(!plugin.stop_virtual_camera("OBS").await.unwrap());
+        assert_eq!(mock.requests_of("StartVirtualCam").len(), 1);
+        assert_eq!(mock.requests_of("StopVirtualCam").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn virtual_camera_status_is_read_from_v4() {
+        let mock = MockObs::start_v4(|request_type, _| match request_type {
+            "GetVirtualCamStatus" => MockReply::Ok(serde_json::json!({ "isVirtualCam": true })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert!(plugin.get_virtual_camera_status("OBS").await.unwrap());
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("restrike_{}_{}", name, Uuid::new_v4()))
+    }
+
+    #[test]
+    fn loopback_hosts_are_local() {
+        assert!(is_loopback_host("localhost"));
+        assert!(is_loopback_host("127.0.0.1"));
+        assert!(is_loopback_host("[::1]"));
+        assert!(!is_loopback_host("192.168.1.20"));
+        assert!(!is_loopback_host("obs-court1.local"));
+    }
+
+    #[test]
+    fn newest_file_is_found() {
+        let directory = temp_dir("newest");
+        std::fs::create_dir_all(directory.join("subdir")).unwrap();
+        std::fs::write(directory.join("old.mkv"), b"").unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        std::fs::write(directory.join("new.mkv"), b"").unwrap();
+
+        let newest = newest_file_in(&directory);
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert!(newest.unwrap().ends_with("new.mkv"));
+        assert_eq!(newest_file_in(&directory), None);
+    }
+
+    #[tokio::test]
+    async fn stop_recording_survives_a_failed_path_lookup() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetRecordDirectory" => MockReply::Fail(600, "No record directory"),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        plugin.add_connection(test_config("OBS", mock.port)).await.unwrap();
+
+        assert_eq!(plugin.stop_recording("OBS").await.unwrap(), None);
+
+        let mut stopped = false;
+        while let Ok(event) = event_rx.try_recv() {
+            stopped |= matches!(event, ObsEvent::RecordingStateChanged { is_recording: false, .. });
+        }
+        assert!(stopped);
+    }
+
+    #[tokio::test]
+    async fn record_stopped_events_store_the_output_path() {
+        let mock = MockObs::start(None, |_, _| MockReply::Ok(serde_json::json!({}))).await;
+        let plugin = connected_plugin(&mock).await;
+
+        mock.emit("RecordStateChanged", serde_json::json!({
+            "outputActive": false,
+            "outputState": "OBS_WEBSOCKET_OUTPUT_STOPPED",
+            "outputPath": "C:/Videos/2026-10-16 10-00-00.mkv"
+        }));
+
+        for _ in 0..50 {
+            if plugin.get_last_recording_path("OBS").is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(plugin.get_last_recording_path("OBS").as_deref(), Some("C:/Videos/2026-10-16 10-00-00.mkv"));
+    }
+
+    #[test]
+    fn auth_response_matches_the_obs_websocket_algorithm() {
+        assert_eq!(
+            obs_auth_response("supersecretpassword", MOCK_SALT, MOCK_CHALLENGE),
+            "1Ct943GAT+6YQUUX47Ia/ncufilbe6+oD6lY+5kaCu4="
+        );
+    }
+
+    #[tokio::test]
+    async fn connection_test_checks_the_password() {
+        let mock = MockObs::start(Some("secret"), |request_type, _| match request_type {
+            "GetVersion" => MockReply::Ok(serde_json::json!({ "obsVersion": "30.1.2" })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+
+        let result = plugin
+            .test_connection_config("127.0.0.1", mock.port, Some("secret"), ObsWebSocketVersion::V5)
+            .await
+            .unwrap();
+        assert_eq!(result.obs_version.as_deref(), Some("30.1.2"));
+        assert_eq!(result.websocket_version.as_deref(), Some("5.4.2"));
+        assert!(result.authentication_required);
+
+        let wrong = plugin
+            .test_connection_config("127.0.0.1", mock.port, Some("wrong"), ObsWebSocketVersion::V5)
+            .await
+            .unwrap_err();
+        assert!(wrong.contains("wrong password"), "{}", wrong);
+
+        let missing = plugin
+            .test_connection_config("127.0.0.1", mock.port, None, ObsWebSocketVersion::V5)
+            .await
+            .unwrap_err();
+        assert!(missing.contains("requires a password"), "{}", missing);
+    }
+
+    #[tokio::test]
+    async fn connection_test_reports_unreachable_hosts() {
+        // Bind and drop to get a port nobody listens on
+        let port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+
+        let error = plugin
+            .test_connection_config("127.0.0.1", port, None, ObsWebSocketVersion::V5)
+            .await
+            .unwrap_err();
+        assert!(error.contains("Connection refused"), "{}", error);
+    }
+
+    #[test]
+    fn recent_frames_are_measured_since_the_previous_sample() {
+        assert_eq!(recent_frames(None, 10, 1000), (10, 1000));
+        assert_eq!(recent_frames(Some((10, 1000)), 60, 2000), (50, 1000));
+        // A new stream resets OBS's counters
+        assert_eq!(recent_frames(Some((10, 1000)), 2, 100), (2, 100));
+
+        assert_eq!(frame_drop_ratio(50, 1000), 0.05);
+        assert_eq!(frame_drop_ratio(0, 0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn worsening_stream_health_emits_a_warning() {
+        // (skipped, total) frame counters reported by successive samples
+        let samples = Arc::new(Mutex::new(vec![(0u64, 1000u64), (5, 2000), (5, 2500), (205, 3000)]));
+        let mock = MockObs::start(None, move |request_type, _| match request_type {
+            "GetStreamStatus" => {
+                let (skipped, total) = samples.lock().unwrap().remove(0);
+                MockReply::Ok(serde_json::json!({
+                    "outputActive": true,
+                    "outputSkippedFrames": skipped,
+                    "outputTotalFrames": total
+                }))
+            }
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        plugin.add_connection(test_config("OBS", mock.port)).await.unwrap();
+
+        let threshold = DEFAULT_STREAM_DROP_THRESHOLD;
+        assert_eq!(plugin.check_stream_health("OBS", threshold).await.unwrap().recent_drop_ratio, 0.0);
+        assert_eq!(plugin.check_stream_health("OBS", threshold).await.unwrap().recent_drop_ratio, 0.005);
+
+        // A UI read in between must not move the monitor's baseline
+        plugin.get_stream_health("OBS").await.unwrap();
+
+        let health = plugin.check_stream_health("OBS", threshold).await.unwrap();
+        assert_eq!(health.recent_drop_ratio, 0.2);
+
+        let warnings: Vec<f64> = std::iter::from_fn(|| event_rx.try_recv().ok())
+            .filter_map(|event| match event {
+                ObsEvent::StreamHealthWarning { drop_ratio, .. } => Some(drop_ratio),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(warnings, vec![0.2]);
+    }
+
+    #[tokio::test]
+    async fn stream_health_monitor_waits_for_the_stream_and_can_be_stopped() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetStreamStatus" => MockReply::Ok(serde_json::json!({ "outputActive": false })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        // An interval of 0 is clamped instead of spinning
+        plugin.start_stream_health_monitor("OBS", Duration::ZERO, DEFAULT_STREAM_DROP_THRESHOLD).unwrap();
+        tokio::time::sleep(MIN_MONITOR_INTERVAL * 3).await;
+        let samples = mock.requests_of("GetStreamStatus").len();
+        assert!((2..=5).contains(&samples), "{}", samples);
+
+        assert!(plugin.stop_stream_health_monitor("OBS").unwrap());
+        tokio::time::sleep(MIN_MONITOR_INTERVAL * 2).await;
+        assert_eq!(mock.requests_of("GetStreamStatus").len(), samples);
+        assert!(!plugin.stop_stream_health_monitor("OBS").unwrap());
+    }
+
+    #[tokio::test]
+    async fn starting_a_second_stream_health_monitor_replaces_the_first() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetStreamStatus" => MockReply::Ok(serde_json::json!({ "outputActive": false })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        plugin.start_stream_health_monitor("OBS", MIN_MONITOR_INTERVAL, DEFAULT_STREAM_DROP_THRESHOLD).unwrap();
+        plugin.start_stream_health_monitor("OBS", MIN_MONITOR_INTERVAL, DEFAULT_STREAM_DROP_THRESHOLD).unwrap();
+        tokio::time::sleep(MIN_MONITOR_INTERVAL * 10).await;
+        plugin.stop_stream_health_monitor("OBS").unwrap();
+
+        // One monitor samples about 10 times in that window; two would double it
+        let samples = mock.requests_of("GetStreamStatus").len();
+        assert!(samples <= 13, "{}", samples);
+    }
+
+    #[test]
+    fn stream_health_monitor_needs_a_runtime() {
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+
+        assert!(plugin.start_stream_health_monitor("OBS", MIN_MONITOR_INTERVAL, DEFAULT_STREAM_DROP_THRESHOLD)
+            .unwrap_err()
+            .contains("runtime"));
+    }
+
+    #[test]
+    fn reconnect_backoff_doubles_up_to_the_maximum() {
+        let config = ObsHeartbeatConfig { interval_ms: 1_000, timeout_ms: 500, max_backoff_ms: 10_000 };
+
+        assert_eq!(config.reconnect_backoff(1), Duration::from_millis(1_000));
+        assert_eq!(config.reconnect_backoff(2), Duration::from_millis(2_000));
+        assert_eq!(config.reconnect_backoff(4), Duration::from_millis(8_000));
+        assert_eq!(config.reconnect_backoff(5), Duration::from_millis(10_000));
+        assert_eq!(config.reconnect_backoff(u32::MAX), Duration::from_millis(10_000));
+    }
+
+    #[test]
+    fn heartbeat_config_is_validated() {
+        assert!(ObsHeartbeatConfig::default().validate().is_ok());
+        assert!(ObsHeartbeatConfig { interval_ms: 0, ..Default::default() }.validate().is_err());
+        assert!(ObsHeartbeatConfig { timeout_ms: 20_000, ..Default::default() }.validate().is_err());
+        assert!(ObsHeartbeatConfig { max_backoff_ms: 1_000, ..Default::default() }.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn heartbeat_keeps_retrying_dead_connections() {
+        let dead_port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        assert!(plugin.add_connection(test_config("OBS", dead_port)).await.is_err());
+
+        let config = ObsHeartbeatConfig::default();
+        assert_eq!(plugin.heartbeat_once(&config).await, vec!["OBS".to_string()]);
+        assert_eq!(plugin.connections.lock().unwrap()["OBS"].reconnect_attempts, 1);
+
+        // Still backing off: no new attempt yet
+        plugin.heartbeat_once(&config).await;
+        assert_eq!(plugin.connections.lock().unwrap()["OBS"].reconnect_attempts, 1);
+
+        // OBS comes back and the backoff elapses
+        let mock = MockObs::start(None, |_, _| MockReply::Ok(serde_json::json!({}))).await;
+        {
+            let mut connections = plugin.connections.lock().unwrap();
+            let connection = connections.get_mut("OBS").unwrap();
+            connection.config.port = mock.port;
+            connection.next_reconnect_at = Some(Instant::now());
+        }
+
+        assert!(plugin.heartbeat_once(&config).await.is_empty());
+        assert_eq!(plugin.get_connection_status("OBS"), Some(ObsConnectionStatus::Authenticated));
+        assert_eq!(plugin.connections.lock().unwrap()["OBS"].reconnect_attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn heartbeat_marks_unresponsive_connections_dead_and_retries_with_backoff() {
+        let mock = MockObs::start(None, |_, _| MockReply::Ok(serde_json::json!({}))).await;
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        plugin.add_connection(test_config("OBS", mock.port)).await.unwrap();
+        // Shorter than set_heartbeat_config allows, to keep the test quick
+        *plugin.heartbeat_config.lock().unwrap() = ObsHeartbeatConfig {
+            interval_ms: 50,
+            timeout_ms: 50,
+            max_backoff_ms: 400,
+        };
+
+        mock.set_silent(true);
+        let heartbeat = tokio::spawn({
+            let plugin = plugin.clone();
+            async move { plugin.run_heartbeat().await }
+        });
+        tokio::time::sleep(Duration::from_millis(600)).await;
+
+        let mut marked_dead = false;
+        while let Ok(event) = event_rx.try_recv() {
+            if let ObsEvent::ConnectionStatusChanged { status: ObsConnectionStatus::Error(error), .. } = event {
+                marked_dead |= error.contains("Heartbeat failed");
+            }
+        }
+        assert!(marked_dead);
+        assert!(matches!(plugin.get_connection_status("OBS"), Some(ObsConnectionStatus::Error(_))));
+        // Backoff doubles (50, 100, 200ms...), so only a few retries fit in the window
+        let attempts = plugin.connections.lock().unwrap()["OBS"].reconnect_attempts;
+        assert!((2..=5).contains(&attempts), "{}", attempts);
+
+        mock.set_silent(false);
+        for _ in 0..100 {
+            if plugin.get_connection_status("OBS") == Some(ObsConnectionStatus::Authenticated) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        heartbeat.abort();
+        assert_eq!(plugin.get_connection_status("OBS"), Some(ObsConnectionStatus::Authenticated));
+        assert_eq!(plugin.connections.lock().unwrap()["OBS"].reconnect_attempts, 0);
+    }
+
+    #[test]
+    fn only_text_sources_accept_text() {
+        assert!(validate_text_source_kind("Score", Some("text_gdiplus_v2")).is_ok());
+        assert!(validate_text_source_kind("Score", Some("text_ft2_source_v2")).is_ok());
+        assert!(validate_text_source_kind("Camera", Some("dshow_input")).unwrap_err().contains("not a text source"));
+        assert!(validate_text_source_kind("Missing", None).unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn text_is_sanitized_before_sending() {
+        assert_eq!(sanitize_text_source("Blue\u{7}\r\n10"), "Blue\n10");
+        assert_eq!(sanitize_text_source(&"x".repeat(1000)).len(), MAX_TEXT_SOURCE_LENGTH);
+    }
+
+    #[tokio::test]
+    async fn text_is_not_written_to_other_source_kinds() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetInputSettings" => MockReply::Ok(serde_json::json!({ "inputKind": "dshow_input", "inputSettings": {} })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert!(plugin.set_text_source("OBS", "Camera", "REC").await.is_err());
+        assert!(mock.requests_of("SetInputSettings").is_empty());
+    }
+
+    #[tokio::test]
+    async fn text_updates_send_the_input_settings_payload() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetInputSettings" => MockReply::Ok(serde_json::json!({ "inputKind": "text_gdiplus_v2", "inputSettings": {} })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert_eq!(plugin.set_text_source("OBS", "Score", "Blue 3\u{7}").await.unwrap(), "Blue 3");
+
+        assert_eq!(mock.requests_of("SetInputSettings"), vec![serde_json::json!({
+            "inputName": "Score",
+            "inputSettings": { "text": "Blue 3" },
+            "overlay": true
+        })]);
+    }
+
+    #[tokio::test]
+    async fn recording_indicator_follows_recording_started_inside_obs() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetInputSettings" => MockReply::Ok(serde_json::json!({ "inputKind": "text_gdiplus_v2", "inputSettings": {} })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let mut config = test_config("OBS", mock.port);
+        config.recording_indicator_source = Some("REC Indicator".to_string());
+        plugin.add_connection(config).await.unwrap();
+
+        mock.emit("RecordStateChanged", serde_json::json!({
+            "outputActive": true,
+            "outputState": "OBS_WEBSOCKET_OUTPUT_STARTED"
+        }));
+        let updates = mock.wait_for_requests("SetInputSettings", 1).await;
+        assert_eq!(updates[0]["inputName"], "REC Indicator");
+        assert_eq!(updates[0]["inputSettings"]["text"], RECORDING_INDICATOR_TEXT);
+
+        mock.emit("RecordStateChanged", serde_json::json!({
+            "outputActive": false,
+            "outputState": "OBS_WEBSOCKET_OUTPUT_STOPPED"
+        }));
+        let updates = mock.wait_for_requests("SetInputSettings", 2).await;
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[1]["inputSettings"]["text"], "");
+    }
+
+    fn test_profile(name: &str) -> QualityProfile {
+        QualityProfile {
+            name: name.to_string(),
+            video_bitrate_kbps: 6000,
+            encoder: "x264".to_string(),
+            preset: "veryfast".to_string(),
+            keyframe_interval_sec: 2,
+        }
+    }
+
+    #[test]
+    fn quality_profiles_validate_keyframe_interval() {
+        assert!(test_profile("Finals").validate().is_ok());
+        assert!(QualityProfile { keyframe_interval_sec: 0, ..test_profile("Finals") }.validate().is_err());
+        assert!(QualityProfile { keyframe_interval_sec: 11, ..test_profile("Finals") }.validate().is_err());
+        assert!(QualityProfile { video_bitrate_kbps: 100, ..test_profile("Finals") }.validate().is_err());
+    }
+
+    #[test]
+    fn x264_options_replace_only_the_given_key() {
+        assert_eq!(with_x264_option("", "keyint", "60"), "keyint=60");
+        assert_eq!(with_x264_option("bframes=2 keyint=250", "keyint", "60"), "bframes=2 keyint=60");
+    }
+
+    #[test]
+    fn quality_profiles_survive_a_restart() {
+        let path = temp_dir("profiles").join("quality_profiles.json");
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        assert_eq!(plugin.load_quality_profiles(&path).unwrap(), 0);
+        plugin.save_quality_profile(test_profile("Finals")).unwrap();
+
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let restarted = ObsPlugin::new(event_tx);
+        assert_eq!(restarted.load_quality_profiles(&path).unwrap(), 1);
+        assert_eq!(restarted.get_quality_profiles(), vec![test_profile("Finals")]);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn quality_profiles_are_not_applied_in_advanced_output_mode() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetProfileParameter" => MockReply::Ok(serde_json::json!({ "parameterValue": "Advanced" })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+        plugin.save_quality_profile(test_profile("Finals")).unwrap();
+
+        let error = plugin.apply_quality_profile("OBS", "Finals").await.unwrap_err();
+        assert!(error.contains("Advanced output mode"), "{}", error);
+        assert!(mock.requests_of("SetProfileParameter").is_empty());
+    }
+
+    #[tokio::test]
+    async fn quality_profiles_set_the_x264_keyframe_interval() {
+        let mock = MockObs::start(None, |request_type, data| match request_type {
+            "GetProfileParameter" if data["parameterName"] == "Mode" => {
+                MockReply::Ok(serde_json::json!({ "parameterValue": "Simple" }))
+            }
+            "GetProfileParameter" => MockReply::Ok(serde_json::json!({ "parameterValue": "bframes=2" })),
+            "GetVideoSettings" => MockReply::Ok(serde_json::json!({
+                "baseWidth": 1920, "baseHeight": 1080, "outputWidth": 1920, "outputHeight": 1080,
+                "fpsNumerator": 30, "fpsDenominator": 1
+            })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+        plugin.save_quality_profile(test_profile("Finals")).unwrap();
+
+        plugin.apply_quality_profile("OBS", "Finals").await.unwrap();
+
+        let x264_settings: Vec<_> = mock.requests_of("SetProfileParameter").into_iter()
+            .filter(|data| data["parameterName"] == "x264Settings")
+            .collect();
+        assert_eq!(x264_settings.len(), 1);
+        assert_eq!(x264_settings[0]["parameterValue"], "bframes=2 keyint=60");
+    }
+
+    #[test]
+    fn presets_use_the_encoders_own_key() {
+        assert_eq!(simple_output_preset_key("x264"), Some("Preset"));
+        assert_eq!(simple_output_preset_key("nvenc"), Some("NVENCPreset2"));
+        assert_eq!(simple_output_preset_key("nvenc_hevc"), Some("NVENCPreset2"));
+        assert_eq!(simple_output_preset_key("qsv"), Some("QSVPreset"));
+        assert_eq!(simple_output_preset_key("amd"), Some("AMDPreset"));
+        assert_eq!(simple_output_preset_key("apple_h264"), None);
+    }
+
+    #[tokio::test]
+    async fn nvenc_profiles_set_the_nvenc_preset() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetProfileParameter" => MockReply::Ok(serde_json::json!({ "parameterValue": "Simple" })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+        plugin.save_quality_profile(QualityProfile {
+            encoder: "nvenc".to_string(),
+            preset: "p5".to_string(),
+            ..test_profile("Finals")
+        }).unwrap();
+
+        plugin.apply_quality_profile("OBS", "Finals").await.unwrap();
+
+        let parameters: Vec<_> = mock.requests_of("SetProfileParameter").into_iter()
+            .map(|data| (data["parameterName"].as_str().unwrap().to_string(), data["parameterValue"].clone()))
+            .collect();
+        assert!(parameters.contains(&("NVENCPreset2".to_string(), serde_json::json!("p5"))));
+        assert!(!parameters.iter().any(|(name, _)| name == "Preset"));
+    }
+
+    #[test]
+    fn quality_profiles_files_load_all_or_nothing() {
+        let path = temp_dir("profiles").join("quality_profiles.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let invalid = QualityProfile { video_bitrate_kbps: 1, ..test_profile("Prelims") };
+        std::fs::write(&path, serde_json::to_string(&vec![test_profile("Finals"), invalid]).unwrap()).unwrap();
+
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        assert!(plugin.load_quality_profiles(&path).unwrap_err().contains("Prelims"));
+        assert!(plugin.get_quality_profiles().is_empty());
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn recording_directory_must_be_an_absolute_directory() {
+        assert!(validate_recording_directory(Path::new("")).is_err());
+        assert!(validate_recording_directory(Path::new("recordings")).unwrap_err().contains("must be absolute"));
+
+        let directory = temp_dir("recordings").join("Court 1");
+        assert_eq!(validate_recording_directory(&directory).unwrap(), directory);
+        assert!(directory.is_dir());
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 0);
+
+        let file = directory.join("match.mkv");
+        std::fs::write(&file, b"").unwrap();
+        assert!(validate_recording_directory(&file).unwrap_err().contains("is not a directory"));
+
+        std::fs::remove_dir_all(directory.parent().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn remote_recording_directories_are_passed_to_obs() {
+        let mock = MockObs::start(None, |_, _| MockReply::Ok(serde_json::json!({}))).await;
+        let plugin = connected_plugin(&mock).await;
+        plugin.connections.lock().unwrap().get_mut("OBS").unwrap().config.host = "192.168.1.20".to_string();
+
+        let directory = plugin.set_recording_directory("OBS", r"D:\Recordings\Court 1").await.unwrap();
+
+        assert_eq!(directory, r"D:\Recordings\Court 1");
+        assert_eq!(mock.requests_of("SetRecordDirectory")[0]["recordDirectory"], r"D:\Recordings\Court 1");
+    }
+
+    #[test]
+    fn only_recording_and_replay_connections_keep_the_replay_buffer_running() {
+        let mut config = test_config("OBS", 4455);
+        config.auto_start_replay_buffer = true;
+        assert!(!config.keeps_replay_buffer_running());
+
+        config.role = Some(ObsConnectionRole::Replay);
+        assert!(config.keeps_replay_buffer_running());
+        config.role = Some(ObsConnectionRole::Recording);
+        assert!(config.keeps_replay_buffer_running());
+        config.role = Some(ObsConnectionRole::Streaming);
+        assert!(!config.keeps_replay_buffer_running());
+
+        config.auto_start_replay_buffer = false;
+        config.role = Some(ObsConnectionRole::Replay);
+        assert!(!config.keeps_replay_buffer_running());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_only_recording_directories_are_rejected() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let directory = temp_dir("read_only");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::set_permissions(&directory, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Root ignores directory permissions, so only check as a normal user
+        let probe = directory.join("probe");
+        if std::fs::write(&probe, b"").is_err() {
+            let error = validate_recording_directory(&directory).unwrap_err();
+            assert!(error.contains("not writable (permission denied)"), "{}", error);
+            let error = validate_recording_directory(&directory.join("Court 1")).unwrap_err();
+            assert!(error.contains("Permission denied creating"), "{}", error);
+        }
+
+        std::fs::set_permissions(&directory, std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    // Mock with scenes "Court 1"/"Replay" whose studio mode state follows requests
+    async fn studio_mode_mock() -> MockObs {
+        let studio_mode = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        MockObs::start(None, move |request_type, data| {
+            use std::sync::atomic::Ordering;
+            match request_type {
+                "SetStudioModeEnabled" => {
+                    studio_mode.store(data["studioModeEnabled"].as_bool().unwrap(), Ordering::SeqCst);
+                    MockReply::Ok(serde_json::json!({}))
+                }
+                "GetStudioModeEnabled" => {
+                    MockReply::Ok(serde_json::json!({ "studioModeEnabled": studio_mode.load(Ordering::SeqCst) }))
+                }
+                "GetSceneList" => MockReply::Ok(serde_json::json!({
+                    "scenes": [{ "sceneName": "Court 1" }, { "sceneName": "Replay" }]
+                })),
+                _ => MockReply::Ok(serde_json::json!({})),
+            }
+        }).await
+    }
+
+    #[tokio::test]
+    async fn studio_mode_stages_a_preview_and_takes_it() {
+        let mock = studio_mode_mock().await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert!(plugin.set_studio_mode("OBS", true).await.unwrap());
+        plugin.set_preview_scene("OBS", "Replay").await.unwrap();
+        plugin.trigger_transition("OBS").await.unwrap();
+
+        assert_eq!(mock.requests_of("SetStudioModeEnabled")[0]["studioModeEnabled"], true);
+        assert_eq!(mock.requests_of("SetCurrentPreviewScene")[0]["sceneName"], "Replay");
+        assert_eq!(mock.requests_of("TriggerStudioModeTransition").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn preview_and_transition_require_studio_mode() {
+        let mock = studio_mode_mock().await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert!(plugin.set_preview_scene("OBS", "Replay").await.unwrap_err().contains("Studio mode must be enabled"));
+        assert!(plugin.trigger_transition("OBS").await.unwrap_err().contains("Studio mode must be enabled"));
+
+        plugin.set_studio_mode("OBS", true).await.unwrap();
+        assert_eq!(plugin.set_preview_scene("OBS", "Court 9").await.unwrap_err(), "Scene 'Court 9' not found");
+        assert!(mock.requests_of("SetCurrentPreviewScene").is_empty());
+        assert!(mock.requests_of("TriggerStudioModeTransition").is_empty());
+    }
+
+    // Mock with profiles "Prelims"/"Finals" whose current profile follows requests
+    async fn profile_mock() -> MockObs {
+        let current = Arc::new(Mutex::new("Prelims".to_string()));
+        MockObs::start(None, move |request_type, data| match request_type {
+            "GetProfileList" => MockReply::Ok(serde_json::json!({
+                "currentProfileName": *current.lock().unwrap(),
+                "profiles": ["Prelims", "Finals"]
+            })),
+            "SetCurrentProfile" => {
+                *current.lock().unwrap() = data["profileName"].as_str().unwrap().to_string();
+                MockReply::Ok(serde_json::json!({}))
+            }
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await
+    }
+
+    #[tokio::test]
+    async fn profiles_are_listed_and_switched() {
+        let mock = profile_mock().await;
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        plugin.add_connection(test_config("OBS", mock.port)).await.unwrap();
+
+        assert_eq!(plugin.get_profiles("OBS").await.unwrap(), vec!["Prelims", "Finals"]);
+        assert_eq!(plugin.get_current_profile("OBS").await.unwrap(), "Prelims");
+        assert_eq!(plugin.set_current_profile("OBS", "Finals").await.unwrap(), "Finals");
+
+        let mut changed_to = None;
+        while let Ok(event) = event_rx.try_recv() {
+            if let ObsEvent::ProfileChanged { profile_name, .. } = event {
+                changed_to = Some(profile_name);
+            }
+        }
+        assert_eq!(changed_to.as_deref(), Some("Finals"));
+    }
+
+    #[tokio::test]
+    async fn unknown_profiles_are_rejected() {
+        let mock = profile_mock().await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert_eq!(plugin.set_current_profile("OBS", "Semifinals").await.unwrap_err(), "Profile 'Semifinals' not found");
+        assert!(mock.requests_of("SetCurrentProfile").is_empty());
+    }
+
+    // Mock that reports a newly selected scene collection only after
+    // `load_polls` more list requests, like OBS still loading it
+    async fn scene_collection_mock(load_polls: usize) -> MockObs {
+        let state = Arc::new(Mutex::new(("Court 1".to_string(), None::<(String, usize)>)));
+        MockObs::start(None, move |request_type, data| {
+            let mut state = state.lock().unwrap();
+            match request_type {
+                "SetCurrentSceneCollection" => {
+                    state.1 = Some((data["sceneCollectionName"].as_str().unwrap().to_string(), load_polls));
+                    MockReply::Ok(serde_json::json!({}))
+                }
+                "GetSceneCollectionList" => {
+                    if let Some((loading, polls_left)) = state.1.take() {
+                        match polls_left {
+                            0 => state.0 = loading,
+                            _ => state.1 = Some((loading, polls_left - 1)),
+                        }
+                    }
+                    MockReply::Ok(serde_json::json!({
+                        "currentSceneCollectionName": state.0,
+                        "sceneCollections": ["Court 1", "Court 2"]
+                    }))
+                }
+                "GetSceneList" => MockReply::Ok(serde_json::json!({
+                    "scenes": [{ "sceneName": format!("{} Main", state.0) }]
+                })),
+                _ => MockReply::Ok(serde_json::json!({})),
+            }
+        }).await
+    }
+
+    #[tokio::test]
+    async fn scene_collections_are_listed_and_switched_after_loading() {
+        let mock = scene_collection_mock(2).await;
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        plugin.add_connection(test_config("OBS", mock.port)).await.unwrap();
+
+        assert_eq!(plugin.get_scene_collections("OBS").await.unwrap(), vec!["Court 1", "Court 2"]);
+        let scenes = plugin.set_scene_collection("OBS", "Court 2").await.unwrap();
+
+        assert_eq!(scenes, vec!["Court 2 Main"]);
+        assert_eq!(plugin.get_current_scene_collection("OBS").await.unwrap(), "Court 2");
+        // The initial list, the validation list, then polls until loaded
+        assert_eq!(mock.requests_of("GetSceneCollectionList").len(), 6);
+
+        let mut completed = false;
+        while let Ok(event) = event_rx.try_recv() {
+            completed |= matches!(event, ObsEvent::SceneCollectionChanged { collection_name, .. } if collection_name == "Court 2");
+        }
+        assert!(completed);
+    }
+
+    #[tokio::test]
+    async fn scene_collection_switch_times_out_if_obs_never_loads_it() {
+        let mock = scene_collection_mock(usize::MAX).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let error = plugin
+            .set_scene_collection_with_timeout("OBS", "Court 2", SCENE_COLLECTION_POLL_INTERVAL * 2)
+            .await
+            .unwrap_err();
+        assert_eq!(error, "Timed out waiting for scene collection 'Court 2' to load");
+        assert_eq!(plugin.set_scene_collection("OBS", "Court 9").await.unwrap_err(), "Scene collection 'Court 9' not found");
+    }
+
+    #[tokio::test]
+    async fn replay_buffer_restarts_when_obs_stops_it() {
+        let mock = MockObs::start(None, |_, _| MockReply::Ok(serde_json::json!({}))).await;
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let mut config = test_config("OBS", mock.port);
+        config.auto_start_replay_buffer = true;
+        config.role = Some(ObsConnectionRole::Replay);
+        plugin.add_connection(config).await.unwrap();
+        assert_eq!(mock.requests_of("StartReplayBuffer").len(), 1);
+
+        mock.emit("ReplayBufferStateChanged", serde_json::json!({
+            "outputActive": false,
+            "outputState": "OBS_WEBSOCKET_OUTPUT_STOPPED"
+        }));
+
+        assert_eq!(mock.wait_for_requests("StartReplayBuffer", 2).await.len(), 2);
+        for _ in 0..50 {
+            if plugin.get_replay_buffer_state("OBS") == Some(true) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(plugin.get_replay_buffer_state("OBS"), Some(true));
+    }
+
+    async fn replay_connection(mock: &MockObs) -> ObsPlugin {
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let mut config = test_config("OBS", mock.port);
+        config.auto_start_replay_buffer = true;
+        config.role = Some(ObsConnectionRole::Replay);
+        plugin.add_connection(config).await.unwrap();
+        plugin
+    }
+
+    fn replay_buffer_stopped_event() -> serde_json::Value {
+        serde_json::json!({ "outputActive": false, "outputState": "OBS_WEBSOCKET_OUTPUT_STOPPED" })
+    }
+
+    #[tokio::test]
+    async fn replay_buffer_stopped_by_the_operator_is_not_restarted_when_the_event_comes_first() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "StopReplayBuffer" => MockReply::Delayed(Duration::from_millis(200), serde_json::json!({})),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = replay_connection(&mock).await;
+
+        let stop = tokio::spawn({
+            let plugin = plugin.clone();
+            async move { plugin.stop_replay_buffer("OBS").await }
+        });
+        mock.wait_for_requests("StopReplayBuffer", 1).await;
+        mock.emit("ReplayBufferStateChanged", replay_buffer_stopped_event());
+
+        stop.await.unwrap().unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(mock.requests_of("StartReplayBuffer").len(), 1);
+        assert_eq!(plugin.get_replay_buffer_state("OBS"), Some(false));
+    }
+
+    #[tokio::test]
+    async fn failed_replay_buffer_stop_keeps_auto_restart() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "StopReplayBuffer" => MockReply::Fail(501, "Replay buffer is not active."),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = replay_connection(&mock).await;
+
+        assert!(plugin.stop_replay_buffer("OBS").await.is_err());
+        mock.emit("ReplayBufferStateChanged", replay_buffer_stopped_event());
+
+        assert_eq!(mock.wait_for_requests("StartReplayBuffer", 2).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn requests_missing_from_available_requests_are_refused_locally() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetVersion" => MockReply::Ok(serde_json::json!({
+                "obsVersion": "27.2.4",
+                "obsWebSocketVersion": "5.0.0",
+                "availableRequests": ["GetVersion", "GetSceneList", "StartRecord", "StopRecord"]
+            })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let config = ObsConnectionConfig {
+            auto_start_replay_buffer: true,
+            role: Some(ObsConnectionRole::Replay),
+            ..test_config("OBS", mock.port)
+        };
+        plugin.add_connection(config).await.unwrap();
+
+        assert_eq!(
+            plugin.start_virtual_camera("OBS").await.unwrap_err(),
+            "Virtual camera is not supported by this OBS version"
+        );
+        assert_eq!(
+            plugin.get_studio_mode_enabled("OBS").await.unwrap_err(),
+            "'GetStudioModeEnabled' is not supported on this OBS version (OBS 27.2.4, obs-websocket 5.0.0)"
+        );
+        assert!(plugin.supports_request("OBS", "StartRecord"));
+        // Nothing unsupported reached OBS, including the optional replay auto-start
+        assert!(mock.requests_of("StartVirtualCam").is_empty());
+        assert!(mock.requests_of("GetStudioModeEnabled").is_empty());
+        assert!(mock.requests_of("StartReplayBuffer").is_empty());
+    }
+
+    #[tokio::test]
+    async fn v4_available_requests_are_read_from_the_comma_separated_list() {
+        let mock = MockObs::start_v4(|request_type, _| match request_type {
+            "GetVersion" => MockReply::Ok(serde_json::json!({
+                "obs-studio-version": "26.1.2",
+                "obs-websocket-version": "4.9.1",
+                "available-requests": "GetVersion,GetSceneList,StartRecording,StopRecording"
+            })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let capabilities = plugin.get_capabilities("OBS").unwrap();
+        assert_eq!(capabilities.available_requests, vec!["GetVersion", "GetSceneList", "StartRecording", "StopRecording"]);
+        assert_eq!(capabilities.obs_version.as_deref(), Some("26.1.2"));
+        assert_eq!(
+            plugin.get_studio_mode_enabled("OBS").await.unwrap_err(),
+            "'GetStudioModeStatus' is not supported on this OBS version (OBS 26.1.2, obs-websocket 4.9.1)"
+        );
+        assert!(mock.requests_of("GetStudioModeStatus").is_empty());
+    }
+
+    #[tokio::test]
+    async fn config_export_captures_inputs_and_stream_service_without_the_key() {
+        let mock = MockObs::start(None, |request_type, data| match request_type {
+            "GetSceneList" => MockReply::Ok(serde_json::json!({ "scenes": [{ "sceneName": "Court 1" }] })),
+            "GetInputList" => MockReply::Ok(serde_json::json!({ "inputs": [
+                { "inputName": "Camera", "inputKind": "dshow_input" },
+                { "inputName": "Scoreboard", "inputKind": "text_gdiplus_v2" }
+            ] })),
+            "GetInputSettings" if data["inputName"] == "Camera" => {
+                MockReply::Ok(serde_json::json!({ "inputSettings": { "video_device_id": "cam0" } }))
+            }
+            "GetInputSettings" => MockReply::Fail(600, "No source was found"),
+            "GetStreamServiceSettings" => MockReply::Ok(serde_json::json!({
+                "streamServiceType": "rtmp_common",
+                "streamServiceSettings": { "service": "YouTube - RTMPS", "key": "secret-key" }
+            })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let snapshot = plugin.export_config("OBS").await.unwrap();
+
+        assert_eq!(snapshot.inputs, vec![ObsInputSnapshot {
+            name: "Camera".to_string(),
+            kind: "dshow_input".to_string(),
+            settings: serde_json::json!({ "video_device_id": "cam0" }),
+        }]);
+        assert!(snapshot.not_exported[0].starts_with("input 'Scoreboard'"));
+        let stream_service = snapshot.stream_service.unwrap();
+        assert_eq!(stream_service.service_type, "rtmp_common");
+        assert!(stream_service.settings.get("key").is_none());
+    }
+
+    #[tokio::test]
+    async fn config_import_reports_missing_inputs_and_empty_scenes() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetSceneList" => MockReply::Ok(serde_json::json!({ "scenes": [] })),
+            "GetInputList" => MockReply::Ok(serde_json::json!({ "inputs": [
+                { "inputName": "Camera", "inputKind": "dshow_input" }
+            ] })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+        let input = |name: &str| ObsInputSnapshot {
+            name: name.to_string(),
+            kind: "dshow_input".to_string(),
+            settings: serde_json::json!({}),
+        };
+        let snapshot = ObsConfigSnapshot {
+            obs_version: None,
+            scene_collection: None,
+            profile: None,
+            scenes: vec!["Court 1".to_string()],
+            current_scene: None,
+            recording_directory: None,
+            studio_mode: None,
+            video_settings: None,
+            inputs: vec![input("Camera"), input("Replay Camera")],
+            stream_service: None,
+            not_exported: Vec::new(),
+        };
+
+        let report = plugin.import_config("OBS", &snapshot).await.unwrap();
+
+        assert!(report.applied.contains(&"scene: Court 1 (created empty)".to_string()));
+        assert!(report.applied.contains(&"input: Camera".to_string()));
+        assert!(report.skipped.contains(&"input 'Replay Camera': not present in this OBS".to_string()));
+        assert_eq!(mock.requests_of("SetInputSettings").len(), 1);
+    }
+
+    #[test]
+    fn timecodes_convert_to_milliseconds() {
+        assert_eq!(parse_timecode_ms("00:00:00.000"), Some(0));
+        assert_eq!(parse_timecode_ms("01:02:03.456"), Some(3_723_456));
+        assert_eq!(parse_timecode_ms("00:10:00"), Some(600_000));
+        assert_eq!(parse_timecode_ms("10:00"), None);
+        assert_eq!(parse_timecode_ms("aa:bb:cc.ddd"), None);
+    }
+
+    #[tokio::test]
+    async fn recording_progress_reports_the_path_from_record_events() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetRecordStatus" => MockReply::Ok(serde_json::json!({
+                "outputActive": true, "outputPaused": false, "outputDuration": 5000, "outputBytes": 1024
+            })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        mock.emit("RecordStateChanged", serde_json::json!({
+            "outputActive": true,
+            "outputState": "OBS_WEBSOCKET_OUTPUT_STARTED",
+            "outputPath": "C:/Videos/court1.mkv"
+        }));
+
+        let mut progress = plugin.get_recording_progress("OBS").await.unwrap();
+        for _ in 0..50 {
+            if progress.output_path.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            progress = plugin.get_recording_progress("OBS").await.unwrap();
+        }
+        assert_eq!(progress.output_path.as_deref(), Some("C:/Videos/court1.mkv"));
+        assert_eq!(progress.elapsed_ms, 5000);
+    }
+
+    #[tokio::test]
+    async fn recording_progress_listener_reports_bytes_per_second_until_recording_stops() {
+        let polls = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let counter = polls.clone();
+        let mock = MockObs::start(None, move |request_type, _| match request_type {
+            "GetRecordStatus" => {
+                let poll = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                if poll < 4 {
+                    MockReply::Ok(serde_json::json!({
+                        "outputActive": true,
+                        "outputPaused": false,
+                        "outputDuration": poll * 100,
+                        "outputBytes": poll * 50_000
+                    }))
+                } else {
+                    MockReply::Ok(serde_json::json!({ "outputActive": false }))
+                }
+            }
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        plugin.add_connection(test_config("OBS", mock.port)).await.unwrap();
+
+        // An interval of 0 is clamped instead of spinning
+        plugin.start_recording_progress_listener("OBS", Duration::ZERO).unwrap();
+        tokio::time::sleep(MIN_MONITOR_INTERVAL * 8).await;
+
+        let progress: Vec<RecordingProgress> = std::iter::from_fn(|| event_rx.try_recv().ok())
+            .filter_map(|event| match event {
+                ObsEvent::RecordingProgress { progress, .. } => Some(progress),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(progress.len(), 4);
+        assert_eq!(progress.iter().map(|p| p.output_bytes).collect::<Vec<_>>(), vec![Some(0), Some(50_000), Some(100_000), Some(150_000)]);
+        // The first sample has nothing to compare against
+        assert_eq!(progress[0].bytes_per_sec, None);
+        for sample in &progress[1..] {
+            let rate = sample.bytes_per_sec.unwrap();
+            assert!(rate > 0.0 && rate <= 500_000.0, "{}", rate);
+        }
+
+        // The listener ended on its own once recording stopped
+        assert_eq!(mock.requests_of("GetRecordStatus").len(), 5);
+        assert!(!plugin.stop_recording_progress_listener("OBS").unwrap());
+    }
+
+    #[tokio::test]
+    async fn recording_progress_listener_can_be_stopped() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetRecordStatus" => MockReply::Ok(serde_json::json!({ "outputActive": true, "outputBytes": 1000 })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        plugin.start_recording_progress_listener("OBS", MIN_MONITOR_INTERVAL).unwrap();
+        plugin.start_recording_progress_listener("OBS", MIN_MONITOR_INTERVAL).unwrap();
+        tokio::time::sleep(MIN_MONITOR_INTERVAL * 3).await;
+        assert!(plugin.stop_recording_progress_listener("OBS").unwrap());
+
+        let samples = mock.requests_of("GetRecordStatus").len();
+        assert!((2..=5).contains(&samples), "{}", samples);
+        tokio::time::sleep(MIN_MONITOR_INTERVAL * 2).await;
+        assert_eq!(mock.requests_of("GetRecordStatus").len(), samples);
+    }
+
+    #[tokio::test]
+    async fn match_breaks_pause_and_resume_the_recording_connection() {
+        let mock = MockObs::start(None, |request_type, _| match request_type {
+            "GetRecordStatus" => MockReply::Ok(serde_json::json!({ "outputActive": true })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let mut config = test_config("Court 1", mock.port);
+        config.pause_recording_on_break = true;
+        config.role = Some(ObsConnectionRole::Recording);
+        plugin.add_connection(config).await.unwrap();
+
+        // Round -> break -> round
+        assert!(!plugin.handle_match_break_for_recording(false).await.unwrap());
+        assert!(plugin.handle_match_break_for_recording(true).await.unwrap());
+        assert!(plugin.handle_match_break_for_recording(false).await.unwrap());
+
+        assert_eq!(mock.requests_of("PauseRecord").len(), 1);
+        assert_eq!(mock.requests_of("ResumeRecord").len(), 1);
+        let spans = plugin.get_recording_pause_spans("Court 1");
+        assert_eq!(spans.len(), 1);
+        assert!(spans[0].resumed_at_ms.is_some());
+    }
+
+    #[tokio::test]
+    async fn v4_match_breaks_pause_and_resume_the_recording_connection() {
+        let mock = MockObs::start_v4(|request_type, _| match request_type {
+            "GetRecordingStatus" => MockReply::Ok(serde_json::json!({
+                "isRecording": true,
+                "isRecordingPaused": false,
+                "recordTimecode": "00:01:00.000"
+            })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let mut config = test_config("Court 1", mock.port);
+        config.protocol_version = ObsWebSocketVersion::V4;
+        config.pause_recording_on_break = true;
+        config.role = Some(ObsConnectionRole::Recording);
+        plugin.add_connection(config).await.unwrap();
+
+        assert!(plugin.get_recording_status("Court 1").await.unwrap());
+
+        // Round -> break -> round
+        assert!(!plugin.handle_match_break_for_recording(false).await.unwrap());
+        assert!(plugin.handle_match_break_for_recording(true).await.unwrap());
+        assert!(plugin.handle_match_break_for_recording(false).await.unwrap());
+
+        assert_eq!(mock.requests_of("PauseRecording").len(), 1);
+        assert_eq!(mock.requests_of("ResumeRecording").len(), 1);
+        let spans = plugin.get_recording_pause_spans("Court 1");
+        assert_eq!(spans.len(), 1);
+        assert!(spans[0].resumed_at_ms.is_some());
+    }
+
+    #[tokio::test]
+    async fn v4_replay_buffer_status_is_read() {
+        let mock = MockObs::start_v4(|request_type, _| match request_type {
+            "GetReplayBufferStatus" => MockReply::Ok(serde_json::json!({ "isReplayBufferActive": true })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert!(plugin.get_replay_buffer_status("OBS").await.unwrap());
+    }
+
+    async fn input_mock() -> MockObs {
+        MockObs::start(None, |request_type, data| match request_type {
+            "GetInputKindList" => MockReply::Ok(serde_json::json!({
+                "inputKinds": ["dshow_input", "image_source", "ffmpeg_source"]
+            })),
+            "GetInputSettings" => match data["inputName"].as_str() {
+                Some("Camera") => MockReply::Ok(serde_json::json!({ "inputKind": "dshow_input", "inputSettings": {} })),
+                Some("Logo") => MockReply::Ok(serde_json::json!({ "inputKind": "image_source", "inputSettings": {} })),
+                _ => MockReply::Fail(600, "No source was found by the name of `inputName`."),
+            },
+            "GetInputPropertiesListPropertyItems" => match data["propertyName"].as_str() {
+                Some("video_device_id") => MockReply::Ok(serde_json::json!({
+                    "propertyItems": [
+                        { "itemName": "Logitech BRIO", "itemValue": "brio:\\\\?\\usb#vid_046d", "itemEnabled": true },
+                        { "itemName": "OBS Virtual Camera", "itemValue": "obs:\\\\?\\root#image", "itemEnabled": false }
+                    ]
+                })),
+                _ => MockReply::Fail(600, "Unable to find a property by that name."),
+            },
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await
+    }
+
+    #[tokio::test]
+    async fn input_kinds_are_listed() {
+        let mock = input_mock().await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert_eq!(plugin.get_input_kinds("OBS").await.unwrap(), vec!["dshow_input", "image_source", "ffmpeg_source"]);
+    }
+
+    #[tokio::test]
+    async fn device_properties_are_listed_for_capture_inputs() {
+        let mock = input_mock().await;
+        let plugin = connected_plugin(&mock).await;
+
+        let devices = plugin.get_input_properties("OBS", "Camera", None).await.unwrap();
+        assert_eq!(devices.iter().map(|d| d.name.as_str()).collect::<Vec<_>>(), vec!["Logitech BRIO", "OBS Virtual Camera"]);
+        assert!(devices[0].enabled);
+        assert!(!devices[1].enabled);
+        assert_eq!(mock.requests_of("GetInputPropertiesListPropertyItems")[0]["propertyName"], "video_device_id");
+    }
+
+    #[tokio::test]
+    async fn inputs_without_devices_list_no_properties() {
+        let mock = input_mock().await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert!(plugin.get_input_properties("OBS", "Logo", None).await.unwrap().is_empty());
+        // An explicitly requested property that doesn't exist is still an error
+        assert_eq!(
+            plugin.get_input_properties("OBS", "Logo", Some("file")).await.unwrap_err(),
+            "GetInputPropertiesListPropertyItems failed: Unable to find a property by that name."
+        );
+        assert!(plugin.get_input_properties("OBS", "Missing", None).await.is_err());
+    }
+
+    #[test]
+    fn stream_keys_are_masked_after_trimming() {
+        assert_eq!(mask_stream_key("abcd-efgh-1234"), "****1234");
+        assert_eq!(mask_stream_key("  abcd-efgh-1234\n"), "****1234");
+        assert_eq!(mask_stream_key("1234"), "****");
+        assert_eq!(mask_stream_key(""), "****");
+    }
+
+    #[test]
+    fn stream_service_settings_are_validated_and_trimmed() {
+        assert_eq!(
+            build_stream_service_settings("custom", " rtmp://live.example.com/app ", " key \n").unwrap(),
+            ("rtmp_custom", serde_json::json!({ "server": "rtmp://live.example.com/app", "key": "key" }))
+        );
+        assert_eq!(
+            build_stream_service_settings("YouTube - RTMPS", "auto", "key").unwrap(),
+            ("rtmp_common", serde_json::json!({ "service": "YouTube - RTMPS", "server": "auto", "key": "key" }))
+        );
+        assert!(build_stream_service_settings("custom", "http://example.com", "key").is_err());
+        assert!(build_stream_service_settings("custom", "rtmp://example.com", "  ").is_err());
+        assert!(build_stream_service_settings("", "auto", "key").is_err());
+    }
+
+    fn video_settings(output_width: u32, output_height: u32, fps_numerator: u32, fps_denominator: u32) -> VideoSettings {
+        VideoSettings {
+            base_width: 1920,
+            base_height: 1080,
+            output_width,
+            output_height,
+            fps_numerator,
+            fps_denominator,
+        }
+    }
+
+    #[test]
+    fn video_settings_validation() {
+        assert_eq!(video_settings(1280, 720, 60000, 1001).validate().unwrap(), Vec::<String>::new());
+        assert!(video_settings(0, 720, 30, 1).validate().is_err());
+        assert_eq!(video_settings(1280, 720, 30, 0).validate().unwrap_err(), "FPS denominator must be positive");
+        assert!(video_settings(1280, 720, 0, 1).validate().unwrap_err().contains("outside the supported range"));
+        assert!(video_settings(1280, 720, 1000, 1).validate().unwrap_err().contains("outside the supported range"));
+
+        let warnings = video_settings(3840, 2160, 30, 1).validate().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("exceeds base resolution"));
+    }
+
+    #[test]
+    fn roles_are_inferred_from_whole_words() {
+        assert_eq!(ObsConnectionRole::infer_from_name("OBS_REC"), ObsConnectionRole::Recording);
+        assert_eq!(ObsConnectionRole::infer_from_name("obs-rec-2"), ObsConnectionRole::Recording);
+        assert_eq!(ObsConnectionRole::infer_from_name("OBS_STR"), ObsConnectionRole::Streaming);
+        assert_eq!(ObsConnectionRole::infer_from_name("Stream PC"), ObsConnectionRole::Streaming);
+        assert_eq!(ObsConnectionRole::infer_from_name("OBS_REPLAY"), ObsConnectionRole::Replay);
+        assert_eq!(ObsConnectionRole::infer_from_name("Director"), ObsConnectionRole::General);
+        assert_eq!(ObsConnectionRole::infer_from_name("Instructor"), ObsConnectionRole::General);
+        assert_eq!(ObsConnectionRole::infer_from_name("Recap"), ObsConnectionRole::General);
+    }
+
+    #[tokio::test]
+    async fn only_one_connection_may_hold_an_explicit_recording_role() {
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let plugin = ObsPlugin::new(event_tx);
+        let config = |name: &str, role: Option<ObsConnectionRole>| ObsConnectionConfig {
+            enabled: false,
+            role,
+            ..test_config(name, 4455)
+        };
+
+        plugin.add_connection(config("Court 1", Some(ObsConnectionRole::Recording))).await.unwrap();
+        let error = plugin.add_connection(config("Court 2", Some(ObsConnectionRole::Recording))).await.unwrap_err();
+        assert!(error.contains("already has the Recording role"), "{}", error);
+
+        // Inferred roles don't conflict but yield to the explicit one
+        plugin.add_connection(config("OBS_REC", None)).await.unwrap();
+        assert_eq!(plugin.get_connections_by_role(ObsConnectionRole::Recording), vec!["Court 1", "OBS_REC"]);
+        assert_eq!(plugin.get_connection_for_role(ObsConnectionRole::Recording).unwrap(), "Court 1");
+        assert!(plugin.get_connection_for_role(ObsConnectionRole::Streaming).is_err());
+    }
+
+    #[tokio::test]
+    async fn video_settings_round_trip() {
+        let current = Arc::new(Mutex::new(serde_json::json!({
+            "baseWidth": 1920, "baseHeight": 1080,
+            "outputWidth": 1920, "outputHeight": 1080,
+            "fpsNumerator": 30, "fpsDenominator": 1
+        })));
+        let state = current.clone();
+        let mock = MockObs::start(None, move |request_type, data| match request_type {
+            "GetVideoSettings" => MockReply::Ok(state.lock().unwrap().clone()),
+            "SetVideoSettings" => {
+                *state.lock().unwrap() = data.clone();
+                MockReply::Ok(serde_json::json!({}))
+            }
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        assert_eq!(plugin.get_video_settings("OBS").await.unwrap(), video_settings(1920, 1080, 30, 1));
+
+        let (applied, warnings) = plugin.set_video_settings("OBS", video_settings(1280, 720, 60000, 1001)).await.unwrap();
+        assert_eq!(applied, video_settings(1280, 720, 60000, 1001));
+        assert!(warnings.is_empty());
+        assert_eq!(mock.requests_of("SetVideoSettings")[0]["fpsDenominator"], 1001);
+
+        // Invalid settings never reach OBS
+        assert!(plugin.set_video_settings("OBS", video_settings(1280, 720, 30, 0)).await.is_err());
+        assert_eq!(mock.requests_of("SetVideoSettings").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn v4_video_settings_convert_fractional_fps() {
+        let mock = MockObs::start_v4(|request_type, _| match request_type {
+            "GetVideoInfo" => MockReply::Ok(serde_json::json!({
+                "baseWidth": 1920, "baseHeight": 1080,
+                "outputWidth": 1280, "outputHeight": 720,
+                "fps": 29.97
+            })),
+            _ => MockReply::Ok(serde_json::json!({})),
+        }).await;
+        let plugin = connected_plugin(&mock).await;
+
+        let settings = plugin.get_video_settings("OBS").await.unwrap();
+        assert_eq!(settings, video_settings(1280, 720, 29970, 1000));
+        assert_eq!(settings.fps_numerator as f64 / settings.fps_denominator as f64, 29.97);
+
+        assert_eq!(
+            plugin.set_video_settings("OBS", settings).await.unwrap_err(),
+            "Changing video settings requires obs-websocket v5"
+        );
+        assert!(mock.requests_of("SetVideoSettings").is_empty());
+    }
+}
@@ -1,4 +1,184 @@
-// Playback plugin stub
+// Playback plugin (mpv)
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+
+// Oldest mpv we support; earlier builds have incomplete JSON IPC
+pub const MIN_MPV_VERSION: (u32, u32, u32) = (0, 33, 0);
+
+// mpv availability and feature report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MpvCapabilities {
+    pub path: String,
+    pub version: Option<String>,
+    pub ipc_supported: bool,
+    pub supported: bool,
+    pub message: String,
+}
+
+// Validation results keyed by mpv path, along with the binary's mtime when checked
+type MpvCapabilitiesCache = HashMap<String, (Option<SystemTime>, MpvCapabilities)>;
+static MPV_CAPABILITIES_CACHE: Mutex<Option<MpvCapabilitiesCache>> = Mutex::new(None);
+
 pub fn playback_clip() {
     // TODO: Implement playback logic
 }
+
+// Check that mpv runs, is recent enough and supports IPC (cached until the binary changes)
+pub fn validate_mpv(path: &str) -> Result<MpvCapabilities, String> {
+    // Upgrading mpv in place changes the mtime, so the cached result is dropped
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(cached) = MPV_CAPABILITIES_CACHE.lock().unwrap()
+        .as_ref()
+        .and_then(|cache| cache.get(path))
+        .filter(|(cached_modified, _)| *cached_modified == modified)
+        .map(|(_, capabilities)| capabilities.clone())
+    {
+        return Ok(cached);
+    }
+
+    let output = Command::new(path)
+        .arg("--version")
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => format!("mpv not found at '{}'. Install mpv or update the mpv path", path),
+            _ => format!("Failed to run mpv at '{}': {}", path, e),
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = parse_mpv_version(&stdout);
+
+    // --input-ipc-server is how we control playback
+    let ipc_supported = Command::new(path)
+        .arg("--list-options")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains("--input-ipc-server"))
+        .unwrap_or(false);
+
+    let version_supported = version
+        .as_deref()
+        .and_then(parse_version_triple)
+        .map(|v| v >= MIN_MPV_VERSION)
+        .unwrap_or(false);
+
+    let minimum = format!("{}.{}.{}", MIN_MPV_VERSION.0, MIN_MPV_VERSION.1, MIN_MPV_VERSION.2);
+    let message = match (&version, version_supported, ipc_supported) {
+        (None, _, _) => "Could not determine mpv version".to_string(),
+        (Some(v), false, _) => format!("mpv {} is too old; version {} or newer is required", v, minimum),
+        (Some(v), true, false) => format!("mpv {} does not support IPC (--input-ipc-server)", v),
+        (Some(v), true, true) => format!("mpv {} is supported", v),
+    };
+
+    let capabilities = MpvCapabilities {
+        path: path.to_string(),
+        version,
+        ipc_supported,
+        supported: version_supported && ipc_supported,
+        message,
+    };
+
+    MPV_CAPABILITIES_CACHE.lock().unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(path.to_string(), (modified, capabilities.clone()));
+
+    Ok(capabilities)
+}
+
+// "mpv 0.35.1 Copyright ..." or "mpv v0.37.0-..." -> "0.35.1"
+fn parse_mpv_version(output: &str) -> Option<String> {
+    let first_line = output.lines().next()?;
+    let mut words = first_line.split_whitespace();
+    if words.next()? != "mpv" {
+        return None;
+    }
+
+    let version = words.next()?.trim_start_matches('v');
+    let version: String = version
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+
+    if version.is_empty() {
+        None
+    } else {
+        Some(version)
+    }
+}
+
+fn parse_version_triple(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.split('.').map(|p| p.parse::<u32>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next()?.ok()?;
+    let patch = parts.next().and_then(|p| p.ok()).unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mpv_versions_are_read_from_the_first_line() {
+        assert_eq!(
+            parse_mpv_version("mpv 0.35.1 Copyright © 2000-2023 mpv/MPlayer/mplayer2 projects\n built on ...").as_deref(),
+            Some("0.35.1")
+        );
+        assert_eq!(parse_mpv_version("mpv v0.37.0-466-g6b5f0ddc6 Copyright © 2000-2024").as_deref(), Some("0.37.0"));
+        assert_eq!(parse_mpv_version("mpv 0.32.0").as_deref(), Some("0.32.0"));
+        assert_eq!(parse_mpv_version("mplayer 1.5"), None);
+        assert_eq!(parse_mpv_version(""), None);
+    }
+
+    #[test]
+    fn version_triples_compare_against_the_minimum() {
+        assert_eq!(parse_version_triple("0.35.1"), Some((0, 35, 1)));
+        assert_eq!(parse_version_triple("0.37"), Some((0, 37, 0)));
+        assert_eq!(parse_version_triple("abc"), None);
+
+        assert!(parse_version_triple("0.35.1").unwrap() >= MIN_MPV_VERSION);
+        assert!(parse_version_triple("0.37.0").unwrap() >= MIN_MPV_VERSION);
+        assert!(parse_version_triple("0.32.0").unwrap() < MIN_MPV_VERSION);
+    }
+
+    #[cfg(unix)]
+    fn write_mock_mpv(path: &std::path::Path, version_line: &str) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = format!(
+            "#!/bin/sh\ncase \"$1\" in\n  --version) echo \"{}\" ;;\n  --list-options) echo \" --input-ipc-server  String\" ;;\nesac\n",
+            version_line
+        );
+        std::fs::write(path, script).unwrap();
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn mock_mpv_versions_are_validated_and_recheck_after_an_upgrade() {
+        let dir = std::env::temp_dir().join(format!("restrike-mpv-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mpv = dir.join("mpv");
+        let path = mpv.to_str().unwrap();
+
+        write_mock_mpv(&mpv, "mpv 0.32.0 Copyright (c) 2000-2020 mpv/MPlayer/mplayer2 projects");
+        let old = validate_mpv(path).unwrap();
+        assert_eq!(old.version.as_deref(), Some("0.32.0"));
+        assert!(old.ipc_supported);
+        assert!(!old.supported);
+        assert_eq!(old.message, "mpv 0.32.0 is too old; version 0.33.0 or newer is required");
+
+        // Replace the binary with a newer mtime, as a package upgrade would
+        write_mock_mpv(&mpv, "mpv 0.35.1 Copyright (c) 2000-2023 mpv/MPlayer/mplayer2 projects");
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options().write(true).open(&mpv).unwrap().set_modified(later).unwrap();
+
+        let new = validate_mpv(path).unwrap();
+        assert_eq!(new.version.as_deref(), Some("0.35.1"));
+        assert!(new.supported);
+        assert_eq!(new.message, "mpv 0.35.1 is supported");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}